@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE steps ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+UPDATE steps
+SET
+    position = (
+        SELECT COUNT(*)
+        FROM steps AS previous_steps
+        WHERE
+            previous_steps.recipe_id = steps.recipe_id
+            AND previous_steps.id < steps.id
+    );
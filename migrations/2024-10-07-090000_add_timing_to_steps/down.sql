@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE steps DROP COLUMN passive;
+
+ALTER TABLE steps DROP COLUMN temperature_celsius;
+
+ALTER TABLE steps DROP COLUMN duration_minutes;
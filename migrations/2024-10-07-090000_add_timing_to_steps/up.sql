@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE steps ADD COLUMN duration_minutes INTEGER;
+
+ALTER TABLE steps ADD COLUMN temperature_celsius INTEGER;
+
+ALTER TABLE steps ADD COLUMN passive BOOLEAN NOT NULL DEFAULT 0;
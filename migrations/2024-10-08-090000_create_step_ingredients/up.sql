@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE step_ingredients (
+    step_id INTEGER NOT NULL,
+    ingredient_id INTEGER NOT NULL,
+    PRIMARY KEY (step_id, ingredient_id),
+    FOREIGN KEY (step_id) REFERENCES steps (id) ON DELETE CASCADE,
+    FOREIGN KEY (ingredient_id) REFERENCES ingredients (id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE recipes DROP COLUMN notes;
+
+ALTER TABLE recipes DROP COLUMN source_url;
+
+ALTER TABLE recipes DROP COLUMN difficulty;
+
+ALTER TABLE recipes DROP COLUMN cook_time_minutes;
+
+ALTER TABLE recipes DROP COLUMN prep_time_minutes;
+
+ALTER TABLE recipes DROP COLUMN description;
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE recipes ADD COLUMN description TEXT;
+
+ALTER TABLE recipes ADD COLUMN prep_time_minutes INTEGER;
+
+ALTER TABLE recipes ADD COLUMN cook_time_minutes INTEGER;
+
+ALTER TABLE recipes ADD COLUMN difficulty TEXT;
+
+ALTER TABLE recipes ADD COLUMN source_url TEXT;
+
+ALTER TABLE recipes ADD COLUMN notes TEXT;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE TABLE tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX recipes_normalized_name;
+
+ALTER TABLE recipes DROP COLUMN normalized_name;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE recipes ADD COLUMN normalized_name TEXT NOT NULL DEFAULT '';
+
+CREATE UNIQUE INDEX recipes_normalized_name ON recipes (normalized_name) WHERE normalized_name <> '';
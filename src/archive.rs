@@ -0,0 +1,352 @@
+use crate::db_utils::{
+    fetch_all_carts, fetch_all_recipes, fetch_matching_cart_id, fetch_recipe_id_by_name,
+    insert_archived_cart, insert_archived_recipe, overwrite_recipe,
+};
+use crate::models::{
+    Archive, ArchivedCart, ArchivedIngredient, ArchivedRecipe, ArchivedStep, ImportReportOut,
+    MergeStrategy, RecipeIn, RecipeWithIngredientsOut, RenamedRecipeOut,
+};
+use crate::response::ErrorDetail;
+use crate::text::normalize_name;
+use crate::validation::{
+    validate_ingredient_names, validate_metadata, validate_name, validate_step,
+};
+
+use diesel::result::Error as DieselError;
+use diesel::{Connection, SqliteConnection};
+use std::collections::HashMap;
+use std::fmt;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum ImportFailure {
+    Invalid(Vec<ErrorDetail>),
+    Database(DieselError),
+}
+
+impl fmt::Display for ImportFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(errors) => write!(
+                f,
+                "Invalid archive: {}",
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            ),
+            Self::Database(error) => write!(f, "Database error: {error}"),
+        }
+    }
+}
+
+fn archive_recipe(recipe: RecipeWithIngredientsOut) -> ArchivedRecipe {
+    let ingredient_ids: Vec<i32> = recipe
+        .ingredients
+        .iter()
+        .map(|ingredient| ingredient.id)
+        .collect();
+
+    ArchivedRecipe {
+        name: recipe.name,
+        description: recipe.description,
+        prep_time_minutes: recipe.prep_time_minutes,
+        cook_time_minutes: recipe.cook_time_minutes,
+        difficulty: recipe.difficulty,
+        source_url: recipe.source_url,
+        notes: recipe.notes,
+        tags: recipe.tags,
+        ingredients: recipe
+            .ingredients
+            .into_iter()
+            .map(|ingredient| ArchivedIngredient {
+                preposition: ingredient.preposition,
+                name: ingredient.name,
+                quantity: ingredient.quantity,
+                unit: ingredient.unit,
+            })
+            .collect(),
+        steps: recipe
+            .steps
+            .into_iter()
+            .map(|step| ArchivedStep {
+                description: step.description,
+                duration_minutes: step.duration_minutes,
+                temperature_celsius: step.temperature_celsius,
+                passive: step.passive,
+                ingredients: step
+                    .ingredient_ids
+                    .iter()
+                    .filter_map(|id| ingredient_ids.iter().position(|other| other == id))
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+pub fn export(connection: &mut SqliteConnection) -> Result<Archive, DieselError> {
+    let recipes = match fetch_all_recipes(connection) {
+        Ok(recipes) => recipes,
+        Err(error) => return Err(error),
+    };
+
+    let carts = match fetch_all_carts(connection) {
+        Ok(carts) => carts,
+        Err(error) => return Err(error),
+    };
+
+    Ok(Archive {
+        version: ARCHIVE_VERSION,
+        exported_at: OffsetDateTime::now_utc().format(&Rfc3339).unwrap(),
+        recipes: recipes.into_iter().map(archive_recipe).collect(),
+        carts: carts
+            .into_iter()
+            .map(|cart| ArchivedCart {
+                created_at: cart.created_at,
+                recipes: cart.recipes.into_iter().map(|recipe| recipe.name).collect(),
+            })
+            .collect(),
+    })
+}
+
+fn parse_created_at(created_at: &str) -> Option<PrimitiveDateTime> {
+    OffsetDateTime::parse(created_at, &Rfc3339)
+        .ok()
+        .map(|created_at| {
+            let created_at = created_at.to_offset(UtcOffset::UTC);
+            PrimitiveDateTime::new(created_at.date(), created_at.time())
+        })
+}
+
+fn validate_archived_recipe(index: usize, recipe: &ArchivedRecipe, errors: &mut Vec<ErrorDetail>) {
+    let recipe_in = RecipeIn {
+        name: recipe.name.clone(),
+        description: recipe.description.clone(),
+        prep_time_minutes: recipe.prep_time_minutes,
+        cook_time_minutes: recipe.cook_time_minutes,
+        difficulty: recipe.difficulty.clone(),
+        source_url: recipe.source_url.clone(),
+        notes: recipe.notes.clone(),
+        tags: recipe.tags.clone(),
+        ..Default::default()
+    };
+
+    errors.extend(
+        validate_name(&recipe.name)
+            .into_iter()
+            .chain(validate_ingredient_names(
+                recipe
+                    .ingredients
+                    .iter()
+                    .map(|ingredient| ingredient.name.as_str()),
+            ))
+            .chain(
+                recipe
+                    .steps
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(step_index, step)| {
+                        validate_step(
+                            &format!("steps[{step_index}].description"),
+                            format!("/steps/{step_index}/description"),
+                            &step.description,
+                        )
+                    }),
+            )
+            .chain(validate_metadata(&recipe_in))
+            .map(|error| error.within("recipes", index)),
+    );
+
+    for (step_index, step) in recipe.steps.iter().enumerate() {
+        for (position, ingredient_index) in step.ingredients.iter().enumerate() {
+            if *ingredient_index >= recipe.ingredients.len() {
+                errors.push(
+                    ErrorDetail::new(
+                        "unknown_ingredient",
+                        format!(
+                            "recipes[{index}].steps[{step_index}] references unknown ingredient {ingredient_index}"
+                        ),
+                    )
+                    .at(format!(
+                        "/recipes/{index}/steps/{step_index}/ingredients/{position}"
+                    )),
+                );
+            }
+        }
+    }
+}
+
+pub fn validate(archive: &Archive) -> Result<(), Vec<ErrorDetail>> {
+    if archive.version != ARCHIVE_VERSION {
+        return Err(vec![ErrorDetail::new(
+            "unsupported_archive_version",
+            format!(
+                "Unsupported archive version {}, expected {ARCHIVE_VERSION}",
+                archive.version
+            ),
+        )
+        .at("/version".to_owned())]);
+    }
+
+    let mut errors = Vec::new();
+    let mut names: Vec<&str> = Vec::with_capacity(archive.recipes.len());
+    let mut normalized_names: Vec<String> = Vec::with_capacity(archive.recipes.len());
+
+    for (index, recipe) in archive.recipes.iter().enumerate() {
+        validate_archived_recipe(index, recipe, &mut errors);
+
+        let normalized_name = normalize_name(&recipe.name);
+
+        if normalized_names.contains(&normalized_name) {
+            errors.push(
+                ErrorDetail::new(
+                    "duplicate_recipe_name",
+                    format!("recipes[{index}].name is duplicated: {}", recipe.name),
+                )
+                .at(format!("/recipes/{index}/name")),
+            );
+        }
+
+        names.push(&recipe.name);
+        normalized_names.push(normalized_name);
+    }
+
+    for (index, cart) in archive.carts.iter().enumerate() {
+        if parse_created_at(&cart.created_at).is_none() {
+            errors.push(
+                ErrorDetail::new(
+                    "invalid_date",
+                    format!(
+                        "carts[{index}].created_at is not an RFC 3339 date: {}",
+                        cart.created_at
+                    ),
+                )
+                .at(format!("/carts/{index}/created_at")),
+            );
+        }
+
+        for (position, name) in cart.recipes.iter().enumerate() {
+            if !names.contains(&name.as_str()) {
+                errors.push(
+                    ErrorDetail::new(
+                        "unknown_recipe",
+                        format!("carts[{index}] references unknown recipe: {name}"),
+                    )
+                    .at(format!("/carts/{index}/recipes/{position}")),
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn available_name(name: &str, connection: &mut SqliteConnection) -> Result<String, DieselError> {
+    let mut suffix = 2;
+
+    loop {
+        let candidate = format!("{name} ({suffix})");
+
+        match fetch_recipe_id_by_name(&candidate, connection) {
+            Ok(None) => return Ok(candidate),
+            Ok(Some(_)) => suffix += 1,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn import_recipes(
+    archive: &Archive,
+    strategy: MergeStrategy,
+    connection: &mut SqliteConnection,
+) -> Result<ImportReportOut, DieselError> {
+    let mut report = ImportReportOut::default();
+    let mut recipe_ids: HashMap<&str, i32> = HashMap::with_capacity(archive.recipes.len());
+
+    for recipe in &archive.recipes {
+        let existing_id = match fetch_recipe_id_by_name(&recipe.name, connection) {
+            Ok(existing_id) => existing_id,
+            Err(error) => return Err(error),
+        };
+
+        let recipe_id = match (existing_id, strategy) {
+            (None, _) => {
+                report.created.push(recipe.name.clone());
+                insert_archived_recipe(&recipe.name, recipe, connection)
+            }
+            (Some(existing_id), MergeStrategy::Skip) => {
+                report.skipped.push(recipe.name.clone());
+                Ok(existing_id)
+            }
+            (Some(existing_id), MergeStrategy::Overwrite) => {
+                report.overwritten.push(recipe.name.clone());
+                overwrite_recipe(existing_id, recipe, connection).map(|()| existing_id)
+            }
+            (Some(_), MergeStrategy::Rename) => {
+                match available_name(recipe.name.trim(), connection) {
+                    Ok(name) => {
+                        let recipe_id = insert_archived_recipe(&name, recipe, connection);
+
+                        report.renamed.push(RenamedRecipeOut {
+                            from: recipe.name.clone(),
+                            to: name,
+                        });
+
+                        recipe_id
+                    }
+                    Err(error) => Err(error),
+                }
+            }
+        };
+
+        match recipe_id {
+            Ok(recipe_id) => recipe_ids.insert(&recipe.name, recipe_id),
+            Err(error) => return Err(error),
+        };
+    }
+
+    for cart in &archive.carts {
+        let Some(created_at) = parse_created_at(&cart.created_at) else {
+            continue;
+        };
+
+        let cart_recipe_ids: Vec<i32> = cart
+            .recipes
+            .iter()
+            .filter_map(|name| recipe_ids.get(name.as_str()).copied())
+            .collect();
+
+        match fetch_matching_cart_id(created_at, &cart_recipe_ids, connection) {
+            Ok(Some(_)) => report.carts_skipped += 1,
+            Ok(None) => match insert_archived_cart(created_at, &cart_recipe_ids, connection) {
+                Ok(_) => report.carts_created += 1,
+                Err(error) => return Err(error),
+            },
+            Err(error) => return Err(error),
+        };
+    }
+
+    Ok(report)
+}
+
+pub fn import(
+    archive: &Archive,
+    strategy: MergeStrategy,
+    connection: &mut SqliteConnection,
+) -> Result<ImportReportOut, ImportFailure> {
+    if let Err(errors) = validate(archive) {
+        return Err(ImportFailure::Invalid(errors));
+    }
+
+    connection
+        .transaction(|connection| import_recipes(archive, strategy, connection))
+        .map_err(ImportFailure::Database)
+}
@@ -0,0 +1,118 @@
+use clap::{Parser, Subcommand};
+use cooking_book::models::{ImportFormat, MergeStrategy};
+use cooking_book::script::{self, RecipeFormat};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(about = "Administer the cooking book without running the HTTP server")]
+struct Cli {
+    /// Database file, overriding the `database.url` setting of Rocket.toml
+    #[arg(long, global = true)]
+    database: Option<String>,
+
+    /// Apply pending database migrations before running the command
+    #[arg(long, global = true)]
+    migrate: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Insert the recipes described in the TOML files of a directory
+    Seed {
+        #[arg(default_value = "seeds")]
+        directory: PathBuf,
+    },
+    /// Add recipes from a JSON, JSON-LD, HTML, Markdown, Cooklang, Paprika or Mealie file
+    AddRecipe {
+        file: String,
+        /// Format of the file, guessed from its extension or content when omitted
+        #[arg(long, value_enum)]
+        format: Option<ImportFormat>,
+    },
+    /// List recipes
+    List {
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Show a recipe
+    Show {
+        recipe_id: i32,
+        #[arg(long, value_enum, default_value_t = RecipeFormat::Text)]
+        format: RecipeFormat,
+    },
+    /// Delete a recipe
+    Delete { recipe_id: i32 },
+    /// Search recipes by name, ingredient or tag
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    /// Manage carts
+    Cart {
+        #[command(subcommand)]
+        command: CartCommand,
+    },
+    /// Print the shopping list of a cart, or write it as a PDF
+    ShoppingList {
+        cart_id: i32,
+        #[arg(long)]
+        pdf: Option<String>,
+    },
+    /// Export the whole cookbook as a JSON archive
+    Export { file: Option<String> },
+    /// Import a JSON archive
+    Import {
+        file: String,
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Skip)]
+        strategy: MergeStrategy,
+    },
+    /// Apply pending database migrations
+    Migrate,
+}
+
+#[derive(Subcommand)]
+enum CartCommand {
+    /// Create an empty cart
+    Create,
+    /// Add a recipe to a cart
+    Add { cart_id: i32, recipe_id: i32 },
+    /// List carts
+    List,
+}
+
+#[cfg(not(tarpaulin_include))]
+fn main() {
+    let cli = Cli::parse();
+
+    let mut connection = script::open_connection(cli.database);
+    let connection = &mut connection;
+
+    if cli.migrate {
+        script::migrate(connection);
+    }
+
+    match cli.command {
+        Command::Seed { directory } => script::create_recipes(connection, &directory),
+        Command::AddRecipe { file, format } => script::add_recipe(connection, &file, format),
+        Command::List { tag } => script::list_recipes(connection, tag.as_deref()),
+        Command::Show { recipe_id, format } => script::show_recipe(connection, recipe_id, format),
+        Command::Delete { recipe_id } => script::remove_recipe(connection, recipe_id),
+        Command::Search { query } => script::search(connection, &query.join(" ")),
+        Command::Cart { command } => match command {
+            CartCommand::Create => script::create_cart(connection),
+            CartCommand::Add { cart_id, recipe_id } => {
+                script::add_to_cart(connection, cart_id, recipe_id);
+            }
+            CartCommand::List => script::list_carts(connection),
+        },
+        Command::ShoppingList { cart_id, pdf } => {
+            script::print_shopping_list(connection, cart_id, pdf.as_deref());
+        }
+        Command::Export { file } => script::export_archive(connection, file.as_deref()),
+        Command::Import { file, strategy } => script::import_archive(connection, &file, strategy),
+        Command::Migrate => script::migrate(connection),
+    }
+}
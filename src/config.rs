@@ -0,0 +1,98 @@
+use rocket::figment::Figment;
+use rocket::serde::Deserialize;
+use std::time::Duration;
+
+pub const DATABASE_KEY: &str = "database";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub pool_size: u32,
+    pub connection_timeout_seconds: u64,
+    pub busy_timeout_milliseconds: u64,
+    pub migrate: bool,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: "cooking_book.db".to_owned(),
+            pool_size: 10,
+            connection_timeout_seconds: 30,
+            busy_timeout_milliseconds: 5000,
+            migrate: true,
+        }
+    }
+}
+
+impl DatabaseSettings {
+    pub fn from_figment(figment: &Figment) -> Result<Self, rocket::figment::Error> {
+        let settings: Self = match figment.focus(DATABASE_KEY).extract() {
+            Ok(settings) => settings,
+            Err(error) => return Err(error),
+        };
+
+        for (field, value) in [
+            ("pool_size", u64::from(settings.pool_size)),
+            (
+                "connection_timeout_seconds",
+                settings.connection_timeout_seconds,
+            ),
+        ] {
+            if value == 0 {
+                return Err(rocket::figment::Error::from(format!(
+                    "{DATABASE_KEY}.{field} must be at least 1"
+                )));
+            }
+        }
+
+        Ok(settings)
+    }
+
+    #[must_use]
+    pub const fn connection_timeout(&self) -> Duration {
+        Duration::from_secs(self.connection_timeout_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+    use rstest::rstest;
+
+    #[test]
+    fn default_config_test() {
+        assert_eq!(
+            DatabaseSettings::from_figment(&Figment::new()).unwrap(),
+            DatabaseSettings::default()
+        );
+    }
+
+    #[test]
+    fn partial_config_test() {
+        let figment = Figment::new().merge(Toml::string(
+            "[database]\nurl = \"/var/lib/cooking_book/staging.db\"\npool_size = 4\n",
+        ));
+
+        assert_eq!(
+            DatabaseSettings::from_figment(&figment).unwrap(),
+            DatabaseSettings {
+                url: "/var/lib/cooking_book/staging.db".to_owned(),
+                pool_size: 4,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("[database]\npool_size = \"many\"\n")]
+    #[case("[database]\npool_size = 0\n")]
+    #[case("[database]\nconnection_timeout_seconds = 0\n")]
+    fn invalid_config_test(#[case] toml: &str) {
+        let figment = Figment::new().merge(Toml::string(toml));
+
+        assert!(DatabaseSettings::from_figment(&figment).is_err());
+    }
+}
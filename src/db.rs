@@ -1,30 +1,334 @@
+use crate::config::DatabaseSettings;
+use crate::schema::recipes;
+use crate::text;
+
+use rocket::fairing::AdHoc;
 use rocket::http::Status;
 
-use diesel::r2d2;
-use diesel::sqlite::SqliteConnection;
+use diesel::migration::MigrationSource;
+use diesel::r2d2::{self, CustomizeConnection};
+use diesel::sql_types::Text;
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use diesel::{define_sql_function, Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
 use rocket::outcome::Outcome;
 use rocket::request::{self, FromRequest};
 use rocket::{Request, State};
 
+use std::error::Error;
 use std::ops::{Deref, DerefMut};
+use std::panic;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 type ConnectionManager = r2d2::ConnectionManager<SqliteConnection>;
 
-type Pool = r2d2::Pool<ConnectionManager>;
+pub type Pool = r2d2::Pool<ConnectionManager>;
+
+const NORMALIZED_NAME_MIGRATION: &str = "20241020090000";
+
+define_sql_function!(fn has_word(text: Text, word: Text) -> Bool);
+
+#[derive(Debug)]
+struct ConnectionOptions {
+    busy_timeout_milliseconds: u64,
+}
+
+impl CustomizeConnection<SqliteConnection, r2d2::Error> for ConnectionOptions {
+    fn on_acquire(&self, connection: &mut SqliteConnection) -> Result<(), r2d2::Error> {
+        match has_word_utils::register_impl(connection, |text: String, word: String| {
+            text::words(&text).contains(&word)
+        }) {
+            Ok(()) => (),
+            Err(error) => return Err(r2d2::Error::QueryError(error)),
+        };
+
+        diesel::sql_query(format!(
+            "PRAGMA busy_timeout = {}",
+            self.busy_timeout_milliseconds
+        ))
+        .execute(connection)
+        .map(|_| ())
+        .map_err(r2d2::Error::QueryError)
+    }
+}
+
+pub fn connect(settings: &DatabaseSettings) -> Result<Pool, r2d2::PoolError> {
+    let manager = r2d2::ConnectionManager::<SqliteConnection>::new(&settings.url);
+
+    r2d2::Pool::builder()
+        .max_size(settings.pool_size)
+        .connection_timeout(settings.connection_timeout())
+        .connection_customizer(Box::new(ConnectionOptions {
+            busy_timeout_milliseconds: settings.busy_timeout_milliseconds,
+        }))
+        .build(manager)
+}
 
 #[must_use]
-pub fn connect(database_url: &str) -> Pool {
-    let manager = r2d2::ConnectionManager::<SqliteConnection>::new(database_url);
-    let Ok(pool) = r2d2::Pool::new(manager) else {
-        panic!("Error connecting to the database");
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Database pool", |rocket| async {
+        let settings = match DatabaseSettings::from_figment(rocket.figment()) {
+            Ok(settings) => settings,
+            Err(error) => {
+                rocket::error!("Invalid database configuration: {error}");
+                return Err(rocket);
+            }
+        };
+
+        match connect(&settings) {
+            Ok(pool) => Ok(rocket.manage(settings).manage(pool)),
+            Err(error) => {
+                rocket::error!("Error connecting to the database {}: {error}", settings.url);
+                Err(rocket)
+            }
+        }
+    })
+}
+
+pub struct SchemaStatus {
+    pub version: Option<String>,
+    pub pending: Vec<String>,
+    pub unknown: Vec<String>,
+}
+
+pub fn schema_status(
+    connection: &mut SqliteConnection,
+) -> Result<SchemaStatus, Box<dyn Error + Send + Sync>> {
+    let embedded: Vec<String> = match MigrationSource::<Sqlite>::migrations(&MIGRATIONS) {
+        Ok(migrations) => migrations
+            .iter()
+            .map(|migration| migration.name().version().to_string())
+            .collect(),
+        Err(error) => return Err(error),
+    };
+
+    let applied: Vec<String> = match connection.applied_migrations() {
+        Ok(versions) => versions.iter().map(ToString::to_string).collect(),
+        Err(error) => return Err(error),
+    };
+
+    Ok(SchemaStatus {
+        version: applied.iter().max().cloned(),
+        pending: embedded
+            .iter()
+            .filter(|version| !applied.contains(version))
+            .cloned()
+            .collect(),
+        unknown: applied
+            .into_iter()
+            .filter(|version| !embedded.contains(version))
+            .collect(),
+    })
+}
+
+fn check_status(status: &SchemaStatus) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if status.unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The database schema is newer than this binary, unknown migrations: {}",
+            status.unknown.join(", ")
+        )
+        .into())
+    }
+}
+
+pub fn check_schema(connection: &mut SqliteConnection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let status = match schema_status(connection) {
+        Ok(status) => status,
+        Err(error) => return Err(error),
+    };
+
+    match check_status(&status) {
+        Ok(()) => (),
+        Err(error) => return Err(error),
+    };
+
+    if status
+        .pending
+        .iter()
+        .any(|version| version == NORMALIZED_NAME_MIGRATION)
+    {
+        Ok(())
+    } else {
+        backfill_normalized_names(connection)
+    }
+}
+
+fn check_name_collisions(
+    connection: &mut SqliteConnection,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let names: Vec<String> = match recipes::table
+        .select(recipes::name)
+        .order(recipes::id)
+        .load(connection)
+    {
+        Ok(names) => names,
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+
+    for name in names {
+        let normalized_name = text::normalize_name(&name);
+
+        match groups
+            .iter_mut()
+            .find(|(other, _)| *other == normalized_name)
+        {
+            Some((_, group)) => group.push(name),
+            None => groups.push((normalized_name, vec![name])),
+        }
+    }
+
+    let collisions: Vec<String> = groups
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(_, group)| format!("\"{}\"", group.join("\", \"")))
+        .collect();
+
+    if collisions.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Recipe names differ only by case, accents or spaces, rename them before migrating: {}",
+            collisions.join("; ")
+        )
+        .into())
+    }
+}
+
+fn backfill_normalized_names(
+    connection: &mut SqliteConnection,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let pending: Vec<(i32, String)> = match recipes::table
+        .filter(recipes::normalized_name.eq(""))
+        .select((recipes::id, recipes::name))
+        .load(connection)
+    {
+        Ok(pending) => pending,
+        Err(error) => return Err(error.into()),
     };
 
-    pool
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    match check_name_collisions(connection) {
+        Ok(()) => (),
+        Err(error) => return Err(error),
+    };
+
+    connection
+        .transaction(|connection| {
+            for (recipe_id, name) in pending {
+                match diesel::update(recipes::table.find(recipe_id))
+                    .set(recipes::normalized_name.eq(text::normalize_name(&name)))
+                    .execute(connection)
+                {
+                    Ok(_) => (),
+                    Err(error) => return Err(error),
+                };
+            }
+
+            Ok(())
+        })
+        .map_err(Into::into)
+}
+
+pub fn run_migrations(
+    connection: &mut SqliteConnection,
+) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    let status = match schema_status(connection) {
+        Ok(status) => status,
+        Err(error) => return Err(error),
+    };
+
+    match check_status(&status) {
+        Ok(()) => (),
+        Err(error) => return Err(error),
+    };
+
+    if status.version.is_some()
+        && status
+            .pending
+            .iter()
+            .any(|version| version == NORMALIZED_NAME_MIGRATION)
+    {
+        match check_name_collisions(connection) {
+            Ok(()) => (),
+            Err(error) => return Err(error),
+        };
+    }
+
+    let versions: Vec<String> = match connection.run_pending_migrations(MIGRATIONS) {
+        Ok(versions) => versions.iter().map(ToString::to_string).collect(),
+        Err(error) => return Err(error),
+    };
+
+    backfill_normalized_names(connection).map(|()| versions)
+}
+
+#[must_use]
+pub fn migrations() -> AdHoc {
+    AdHoc::try_on_ignite("Database migrations", |rocket| async {
+        let migrated = match (rocket.state::<Pool>(), rocket.state::<DatabaseSettings>()) {
+            (Some(pool), Some(settings)) => {
+                pool.get()
+                    .map_err(|error| error.to_string())
+                    .and_then(|mut connection| {
+                        if settings.migrate {
+                            run_migrations(&mut connection)
+                        } else {
+                            check_schema(&mut connection).map(|()| Vec::new())
+                        }
+                        .map_err(|error| error.to_string())
+                    })
+            }
+            _ => Err("The database pool is not configured".to_owned()),
+        };
+
+        match migrated {
+            Ok(versions) => {
+                for version in versions {
+                    rocket::info!("Applied migration {version}");
+                }
+
+                Ok(rocket)
+            }
+            Err(error) => {
+                rocket::error!("Error migrating the database: {error}");
+                Err(rocket)
+            }
+        }
+    })
+}
+
+pub async fn run_blocking<F, T>(function: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match rocket::tokio::task::spawn_blocking(function).await {
+        Ok(value) => value,
+        Err(error) => panic::resume_unwind(error.into_panic()),
+    }
 }
 
 pub struct DBConnection(pub r2d2::PooledConnection<ConnectionManager>);
 
+impl DBConnection {
+    pub async fn run<F, T>(mut self, function: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        run_blocking(move || function(&mut self)).await
+    }
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for DBConnection {
     type Error = ();
@@ -33,7 +337,17 @@ impl<'r> FromRequest<'r> for DBConnection {
         let pool = request.guard::<&State<Pool>>().await;
 
         match pool {
-            Outcome::Success(pool) => Outcome::Success(Self(pool.get().unwrap())),
+            Outcome::Success(pool) => {
+                let pool = pool.inner().clone();
+
+                match run_blocking(move || pool.get()).await {
+                    Ok(connection) => Outcome::Success(Self(connection)),
+                    Err(error) => {
+                        rocket::warn!("No database connection available: {error}");
+                        Outcome::Error((Status::ServiceUnavailable, ()))
+                    }
+                }
+            }
             Outcome::Error(_) => Outcome::Error((Status::InternalServerError, ())),
             Outcome::Forward(_) => Outcome::Forward(Status::ServiceUnavailable),
         }
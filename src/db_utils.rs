@@ -1,49 +1,100 @@
+use crate::db::has_word;
+use crate::ingredient_formatter;
 use crate::ingredient_parser::parse;
 use crate::models::{
-    Cart, CartRecipe, CartWithRecipesOut, Ingredient, IngredientOut, Recipe, RecipeIn,
-    RecipeWithIngredientsOut, Step,
+    ArchivedRecipe, Cart, CartRecipe, CartWithRecipesOut, Ingredient, IngredientOut, Recipe,
+    RecipeIn, RecipeTag, RecipeWithIngredientsOut, Step, StepIngredient, StepOut, TagOut,
 };
-use crate::schema::{cart_recipes, carts, ingredients, recipes, steps};
+use crate::schema::{
+    cart_recipes, carts, ingredients, recipe_tags, recipes, step_ingredients, steps, tags,
+};
+use crate::step_parser;
+use crate::text::{normalize_name, slug, words};
 
 use diesel::prelude::*;
 use diesel::result::Error as DieselError;
 use time::format_description::well_known::Rfc3339;
+use time::PrimitiveDateTime;
 
 pub fn insert_recipe(
     recipe_in: &RecipeIn,
     connection: &mut SqliteConnection,
 ) -> Result<RecipeWithIngredientsOut, DieselError> {
-    let id: i32 = match diesel::insert_into(recipes::table)
-        .values(&recipes::name.eq(&recipe_in.name))
-        .returning(&recipes::id)
+    let recipe: Recipe = match diesel::insert_into(recipes::table)
+        .values((
+            &recipes::name.eq(recipe_in.name.trim()),
+            &recipes::normalized_name.eq(normalize_name(&recipe_in.name)),
+            &recipes::description.eq(&recipe_in.description),
+            &recipes::prep_time_minutes.eq(&recipe_in.prep_time_minutes),
+            &recipes::cook_time_minutes.eq(&recipe_in.cook_time_minutes),
+            &recipes::difficulty.eq(&recipe_in.difficulty),
+            &recipes::source_url.eq(&recipe_in.source_url),
+            &recipes::notes.eq(&recipe_in.notes),
+        ))
+        .returning(Recipe::as_returning())
         .get_result(connection)
     {
         Ok(res) => res,
         Err(error) => return Err(error),
     };
 
-    let mut recipe_out = RecipeWithIngredientsOut {
-        id,
-        name: recipe_in.name.clone(),
-        ingredients: Vec::with_capacity(recipe_in.ingredients.len()),
-        steps: Vec::with_capacity(recipe_in.steps.len()),
-    };
+    let mut ingredients_out = Vec::with_capacity(recipe_in.ingredients.len());
 
     for raw_ingredient in &recipe_in.ingredients {
-        match insert_ingredient(recipe_out.id, raw_ingredient, connection) {
-            Ok(ingredient) => recipe_out.ingredients.push(ingredient),
+        match insert_ingredient(recipe.id, raw_ingredient, connection) {
+            Ok(ingredient) => ingredients_out.push(ingredient),
             Err(error) => return Err(error),
         };
     }
 
-    for step in &recipe_in.steps {
-        match insert_step(recipe_out.id, step, connection) {
-            Ok(step) => recipe_out.steps.push(step),
+    let mut steps_out = Vec::with_capacity(recipe_in.steps.len());
+
+    for (position, step) in (0..).zip(&recipe_in.steps) {
+        match insert_step(recipe.id, position, step, &ingredients_out, connection) {
+            Ok(step) => steps_out.push(step),
             Err(error) => return Err(error),
         };
     }
 
-    Ok(recipe_out)
+    let tags = match insert_recipe_tags(recipe.id, &recipe_in.tags, connection) {
+        Ok(tags) => tags,
+        Err(error) => return Err(error),
+    };
+
+    Ok(build_recipe_out(recipe, tags, ingredients_out, steps_out))
+}
+
+fn build_recipe_out(
+    recipe: Recipe,
+    tags: Vec<String>,
+    ingredients: Vec<IngredientOut>,
+    steps: Vec<StepOut>,
+) -> RecipeWithIngredientsOut {
+    let (passive_steps, active_steps): (Vec<&StepOut>, Vec<&StepOut>) =
+        steps.iter().partition(|step| step.passive);
+
+    let total_duration = |steps: Vec<&StepOut>| -> i32 {
+        steps
+            .iter()
+            .filter_map(|step| step.duration_minutes)
+            .fold(0, i32::saturating_add)
+    };
+
+    RecipeWithIngredientsOut {
+        id: recipe.id,
+        name: recipe.name,
+        description: recipe.description,
+        prep_time_minutes: recipe.prep_time_minutes,
+        cook_time_minutes: recipe.cook_time_minutes,
+        difficulty: recipe.difficulty,
+        source_url: recipe.source_url,
+        notes: recipe.notes,
+        tags,
+        ingredients,
+        active_time_minutes: total_duration(active_steps),
+        passive_time_minutes: total_duration(passive_steps),
+        steps,
+    }
 }
 
 fn insert_ingredient(
@@ -69,6 +120,7 @@ fn insert_ingredient(
     };
 
     Ok(IngredientOut {
+        display: ingredient_formatter::format_parts(&name, quantity, &unit),
         id,
         preposition,
         name,
@@ -79,22 +131,50 @@ fn insert_ingredient(
 
 fn insert_step(
     recipe_id: i32,
+    position: i32,
     step: &str,
+    ingredients: &[IngredientOut],
     connection: &mut SqliteConnection,
-) -> Result<String, DieselError> {
-    let _: i32 = match diesel::insert_into(steps::table)
+) -> Result<StepOut, DieselError> {
+    let timing = step_parser::parse(step);
+
+    let step: Step = match diesel::insert_into(steps::table)
         .values((
             &steps::recipe_id.eq(&recipe_id),
             &steps::description.eq(&step),
+            &steps::position.eq(&position),
+            &steps::duration_minutes.eq(&timing.duration_minutes),
+            &steps::temperature_celsius.eq(&timing.temperature_celsius),
+            &steps::passive.eq(&timing.passive),
         ))
-        .returning(&steps::id)
+        .returning(Step::as_returning())
         .get_result(connection)
     {
         Ok(res) => res,
         Err(error) => return Err(error),
     };
 
-    Ok(step.to_string())
+    let ingredient_ids = step_parser::mentioned_ingredients(&step.description, ingredients);
+
+    let step_ingredients: Vec<_> = ingredient_ids
+        .iter()
+        .map(|ingredient_id| {
+            (
+                step_ingredients::step_id.eq(step.id),
+                step_ingredients::ingredient_id.eq(ingredient_id),
+            )
+        })
+        .collect();
+
+    match diesel::insert_into(step_ingredients::table)
+        .values(&step_ingredients)
+        .execute(connection)
+    {
+        Ok(_) => (),
+        Err(error) => return Err(error),
+    };
+
+    Ok((step, ingredient_ids).into())
 }
 
 pub fn fetch_one_recipe(
@@ -110,6 +190,18 @@ pub fn fetch_one_recipe(
         Err(error) => return Err(error),
     };
 
+    fetch_recipe_out(recipe, connection)
+}
+
+fn fetch_recipe_out(
+    recipe: Recipe,
+    connection: &mut SqliteConnection,
+) -> Result<RecipeWithIngredientsOut, DieselError> {
+    let tags = match fetch_recipe_tags(&recipe, connection) {
+        Ok(tags) => tags,
+        Err(error) => return Err(error),
+    };
+
     let ingredients_out = match fetch_recipe_ingredients(&recipe, connection) {
         Ok(ingredients) => ingredients,
         Err(error) => return Err(error),
@@ -120,42 +212,209 @@ pub fn fetch_one_recipe(
         Err(error) => return Err(error),
     };
 
-    Ok(RecipeWithIngredientsOut {
-        id: recipe.id,
-        name: recipe.name,
-        ingredients: ingredients_out,
-        steps,
-    })
+    Ok(build_recipe_out(recipe, tags, ingredients_out, steps))
 }
 
 fn fetch_recipe_ingredients(
     recipe: &Recipe,
     connection: &mut SqliteConnection,
 ) -> Result<Vec<IngredientOut>, DieselError> {
-    let ingredients_out = match Ingredient::belonging_to(&recipe)
-        .select(IngredientOut::as_select())
+    let ingredients = match Ingredient::belonging_to(&recipe)
+        .select(Ingredient::as_select())
         .load(connection)
     {
         Ok(ingredients) => ingredients,
         Err(error) => return Err(error),
     };
 
-    Ok(ingredients_out)
+    Ok(ingredients.into_iter().map(IngredientOut::from).collect())
 }
 
 fn fetch_step_ingredients(
     recipe: &Recipe,
     connection: &mut SqliteConnection,
-) -> Result<Vec<String>, DieselError> {
+) -> Result<Vec<StepOut>, DieselError> {
     let steps = match Step::belonging_to(&recipe)
         .select(Step::as_select())
+        .order_by(steps::position)
         .load(connection)
     {
         Ok(steps) => steps,
         Err(error) => return Err(error),
     };
 
-    Ok(steps.into_iter().map(|value| value.description).collect())
+    let step_ingredients = match StepIngredient::belonging_to(&steps)
+        .select(StepIngredient::as_select())
+        .order_by(step_ingredients::ingredient_id)
+        .load(connection)
+    {
+        Ok(step_ingredients) => step_ingredients,
+        Err(error) => return Err(error),
+    };
+
+    Ok(step_ingredients
+        .grouped_by(&steps)
+        .into_iter()
+        .zip(steps)
+        .map(|(step_ingredients, step)| {
+            let ingredient_ids = step_ingredients
+                .into_iter()
+                .map(|step_ingredient| step_ingredient.ingredient_id)
+                .collect();
+
+            (step, ingredient_ids).into()
+        })
+        .collect())
+}
+
+fn count_recipe_steps(
+    recipe_id: i32,
+    connection: &mut SqliteConnection,
+) -> Result<i32, DieselError> {
+    let count: i64 = match steps::table
+        .filter(steps::recipe_id.eq(recipe_id))
+        .count()
+        .get_result(connection)
+    {
+        Ok(count) => count,
+        Err(error) => return Err(error),
+    };
+
+    i32::try_from(count).map_err(|_| DieselError::NotFound)
+}
+
+pub fn insert_step_at_position(
+    recipe_id: i32,
+    position: i32,
+    description: &str,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    connection.transaction(|connection| {
+        let recipe = match recipes::table
+            .filter(recipes::id.eq(recipe_id))
+            .select(Recipe::as_select())
+            .first(connection)
+        {
+            Ok(recipe) => recipe,
+            Err(error) => return Err(error),
+        };
+
+        let ingredients = match fetch_recipe_ingredients(&recipe, connection) {
+            Ok(ingredients) => ingredients,
+            Err(error) => return Err(error),
+        };
+
+        let step_count = match count_recipe_steps(recipe_id, connection) {
+            Ok(count) => count,
+            Err(error) => return Err(error),
+        };
+
+        if !(0..=step_count).contains(&position) {
+            return Err(DieselError::NotFound);
+        }
+
+        match diesel::update(
+            steps::table
+                .filter(steps::recipe_id.eq(recipe_id))
+                .filter(steps::position.ge(position)),
+        )
+        .set(steps::position.eq(steps::position + 1))
+        .execute(connection)
+        {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+
+        match insert_step(recipe_id, position, description, &ingredients, connection) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(error),
+        }
+    })
+}
+
+pub fn move_step_to_position(
+    recipe_id: i32,
+    position: i32,
+    new_position: i32,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    connection.transaction(|connection| {
+        let step_id: i32 = match steps::table
+            .filter(steps::recipe_id.eq(recipe_id))
+            .filter(steps::position.eq(position))
+            .select(steps::id)
+            .first(connection)
+        {
+            Ok(id) => id,
+            Err(error) => return Err(error),
+        };
+
+        let step_count = match count_recipe_steps(recipe_id, connection) {
+            Ok(count) => count,
+            Err(error) => return Err(error),
+        };
+
+        if !(0..step_count).contains(&new_position) {
+            return Err(DieselError::NotFound);
+        }
+
+        let recipe_steps = steps::table.filter(steps::recipe_id.eq(recipe_id));
+
+        let shifted_records = if position < new_position {
+            diesel::update(
+                recipe_steps
+                    .filter(steps::position.gt(position))
+                    .filter(steps::position.le(new_position)),
+            )
+            .set(steps::position.eq(steps::position - 1))
+            .execute(connection)
+        } else {
+            diesel::update(
+                recipe_steps
+                    .filter(steps::position.ge(new_position))
+                    .filter(steps::position.lt(position)),
+            )
+            .set(steps::position.eq(steps::position + 1))
+            .execute(connection)
+        };
+
+        match shifted_records {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+
+        diesel::update(steps::table.filter(steps::id.eq(step_id)))
+            .set(steps::position.eq(new_position))
+            .execute(connection)
+            .map(|_| ())
+    })
+}
+
+pub fn delete_step_at_position(
+    recipe_id: i32,
+    position: i32,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    connection.transaction(|connection| {
+        let recipe_steps = steps::table.filter(steps::recipe_id.eq(recipe_id));
+
+        let deleted_records =
+            match diesel::delete(recipe_steps.filter(steps::position.eq(position)))
+                .execute(connection)
+            {
+                Ok(deleted_records) => deleted_records,
+                Err(error) => return Err(error),
+            };
+
+        if deleted_records == 0 {
+            return Err(DieselError::NotFound);
+        }
+
+        diesel::update(recipe_steps.filter(steps::position.gt(position)))
+            .set(steps::position.eq(steps::position - 1))
+            .execute(connection)
+            .map(|_| ())
+    })
 }
 
 pub fn fetch_all_recipes(
@@ -166,28 +425,265 @@ pub fn fetch_all_recipes(
         Err(error) => return Err(error),
     };
 
+    fetch_recipes_out(recipes, connection)
+}
+
+pub fn fetch_recipes_with_tag(
+    tag: &str,
+    connection: &mut SqliteConnection,
+) -> Result<Vec<RecipeWithIngredientsOut>, DieselError> {
+    let recipes = match recipes::table
+        .inner_join(recipe_tags::table.inner_join(tags::table))
+        .filter(tags::slug.eq(slug(tag)))
+        .select(Recipe::as_select())
+        .order_by(recipes::id)
+        .load(connection)
+    {
+        Ok(recipe) => recipe,
+        Err(error) => return Err(error),
+    };
+
+    fetch_recipes_out(recipes, connection)
+}
+
+pub fn search_recipes(
+    query: &str,
+    connection: &mut SqliteConnection,
+) -> Result<Vec<RecipeWithIngredientsOut>, DieselError> {
+    let mut matching_recipes = recipes::table
+        .select(Recipe::as_select())
+        .order_by(recipes::id)
+        .into_boxed();
+
+    for word in words(query) {
+        let ingredient_matches = ingredients::table
+            .filter(has_word(ingredients::name, word.clone()))
+            .select(ingredients::recipe_id);
+        let tag_matches = recipe_tags::table
+            .inner_join(tags::table)
+            .filter(has_word(tags::name, word.clone()))
+            .select(recipe_tags::recipe_id);
+
+        matching_recipes = matching_recipes.filter(
+            has_word(recipes::name, word)
+                .or(recipes::id.eq_any(ingredient_matches))
+                .or(recipes::id.eq_any(tag_matches)),
+        );
+    }
+
+    let recipes = match matching_recipes.load(connection) {
+        Ok(recipes) => recipes,
+        Err(error) => return Err(error),
+    };
+
+    fetch_recipes_out(recipes, connection)
+}
+
+pub fn delete_recipe(
+    recipe_id: i32,
+    connection: &mut SqliteConnection,
+) -> Result<usize, DieselError> {
+    connection.transaction(|connection| {
+        let recipe_steps = steps::table
+            .filter(steps::recipe_id.eq(recipe_id))
+            .select(steps::id);
+
+        for result in [
+            diesel::delete(
+                step_ingredients::table.filter(step_ingredients::step_id.eq_any(recipe_steps)),
+            )
+            .execute(connection),
+            diesel::delete(steps::table.filter(steps::recipe_id.eq(recipe_id))).execute(connection),
+            diesel::delete(ingredients::table.filter(ingredients::recipe_id.eq(recipe_id)))
+                .execute(connection),
+            diesel::delete(recipe_tags::table.filter(recipe_tags::recipe_id.eq(recipe_id)))
+                .execute(connection),
+            diesel::delete(cart_recipes::table.filter(cart_recipes::recipe_id.eq(recipe_id)))
+                .execute(connection),
+        ] {
+            match result {
+                Ok(_) => (),
+                Err(error) => return Err(error),
+            };
+        }
+
+        diesel::delete(recipes::table.filter(recipes::id.eq(recipe_id))).execute(connection)
+    })
+}
+
+fn fetch_recipes_out(
+    recipes: Vec<Recipe>,
+    connection: &mut SqliteConnection,
+) -> Result<Vec<RecipeWithIngredientsOut>, DieselError> {
     let mut recipes_with_ingredients = Vec::with_capacity(recipes.len());
 
     for recipe in recipes {
-        let ingredients = match fetch_recipe_ingredients(&recipe, connection) {
-            Ok(ingredients) => ingredients,
+        match fetch_recipe_out(recipe, connection) {
+            Ok(recipe) => recipes_with_ingredients.push(recipe),
             Err(error) => return Err(error),
         };
+    }
 
-        let steps = match fetch_step_ingredients(&recipe, connection) {
-            Ok(steps) => steps,
+    Ok(recipes_with_ingredients)
+}
+
+fn normalize_tag_name(raw_tag: &str) -> String {
+    raw_tag
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn insert_recipe_tags(
+    recipe_id: i32,
+    raw_tags: &[String],
+    connection: &mut SqliteConnection,
+) -> Result<Vec<String>, DieselError> {
+    let mut tag_ids = Vec::with_capacity(raw_tags.len());
+
+    for raw_tag in raw_tags {
+        let name = normalize_tag_name(raw_tag);
+        let tag_slug = slug(&name);
+
+        match diesel::insert_into(tags::table)
+            .values((tags::name.eq(&name), tags::slug.eq(&tag_slug)))
+            .on_conflict(tags::slug)
+            .do_nothing()
+            .execute(connection)
+        {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+
+        let tag_id: i32 = match tags::table
+            .filter(tags::slug.eq(&tag_slug))
+            .select(tags::id)
+            .first(connection)
+        {
+            Ok(tag_id) => tag_id,
             Err(error) => return Err(error),
         };
 
-        recipes_with_ingredients.push(RecipeWithIngredientsOut {
-            id: recipe.id,
-            name: recipe.name,
-            ingredients,
-            steps,
-        });
+        if !tag_ids.contains(&tag_id) {
+            tag_ids.push(tag_id);
+        }
     }
 
-    Ok(recipes_with_ingredients)
+    let recipe_tags: Vec<_> = tag_ids
+        .iter()
+        .map(|tag_id| {
+            (
+                recipe_tags::recipe_id.eq(recipe_id),
+                recipe_tags::tag_id.eq(tag_id),
+            )
+        })
+        .collect();
+
+    match diesel::insert_into(recipe_tags::table)
+        .values(&recipe_tags)
+        .execute(connection)
+    {
+        Ok(_) => (),
+        Err(error) => return Err(error),
+    };
+
+    tags::table
+        .filter(tags::id.eq_any(&tag_ids))
+        .select(tags::name)
+        .order_by(tags::name)
+        .load(connection)
+}
+
+fn fetch_recipe_tags(
+    recipe: &Recipe,
+    connection: &mut SqliteConnection,
+) -> Result<Vec<String>, DieselError> {
+    RecipeTag::belonging_to(recipe)
+        .inner_join(tags::table)
+        .select(tags::name)
+        .order_by(tags::name)
+        .load(connection)
+}
+
+pub fn fetch_all_tags(connection: &mut SqliteConnection) -> Result<Vec<TagOut>, DieselError> {
+    tags::table
+        .left_join(recipe_tags::table)
+        .group_by(tags::id)
+        .select((
+            tags::id,
+            tags::name,
+            diesel::dsl::count(recipe_tags::recipe_id.nullable()),
+        ))
+        .order_by(tags::name)
+        .load(connection)
+}
+
+pub fn fetch_one_tag(
+    tag_id: i32,
+    connection: &mut SqliteConnection,
+) -> Result<TagOut, DieselError> {
+    tags::table
+        .left_join(recipe_tags::table)
+        .filter(tags::id.eq(tag_id))
+        .group_by(tags::id)
+        .select((
+            tags::id,
+            tags::name,
+            diesel::dsl::count(recipe_tags::recipe_id.nullable()),
+        ))
+        .first(connection)
+}
+
+pub fn rename_tag(
+    tag_id: i32,
+    raw_name: &str,
+    connection: &mut SqliteConnection,
+) -> Result<usize, DieselError> {
+    let name = normalize_tag_name(raw_name);
+
+    diesel::update(tags::table.filter(tags::id.eq(tag_id)))
+        .set((tags::slug.eq(slug(&name)), tags::name.eq(&name)))
+        .execute(connection)
+}
+
+pub fn merge_tags(
+    tag_id: i32,
+    target_tag_id: i32,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    connection.transaction(|connection| {
+        for id in [tag_id, target_tag_id] {
+            match tags::table
+                .filter(tags::id.eq(id))
+                .select(tags::id)
+                .first::<i32>(connection)
+            {
+                Ok(_) => (),
+                Err(error) => return Err(error),
+            };
+        }
+
+        match diesel::insert_or_ignore_into(recipe_tags::table)
+            .values(
+                recipe_tags::table
+                    .filter(recipe_tags::tag_id.eq(tag_id))
+                    .select((
+                        recipe_tags::recipe_id,
+                        target_tag_id.into_sql::<diesel::sql_types::Integer>(),
+                    )),
+            )
+            .into_columns((recipe_tags::recipe_id, recipe_tags::tag_id))
+            .execute(connection)
+        {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+
+        diesel::delete(tags::table.filter(tags::id.eq(tag_id)))
+            .execute(connection)
+            .map(|_| ())
+    })
 }
 
 pub fn fetch_one_cart(
@@ -271,3 +767,245 @@ pub fn insert_into_cart(
         ))
         .execute(connection)
 }
+
+pub fn fetch_all_carts(
+    connection: &mut SqliteConnection,
+) -> Result<Vec<CartWithRecipesOut>, DieselError> {
+    let cart_ids: Vec<i32> = match carts::table
+        .select(carts::id)
+        .order_by(carts::id)
+        .load(connection)
+    {
+        Ok(cart_ids) => cart_ids,
+        Err(error) => return Err(error),
+    };
+
+    let mut carts_out = Vec::with_capacity(cart_ids.len());
+
+    for cart_id in cart_ids {
+        match fetch_one_cart_and_recipes(cart_id, connection) {
+            Ok(cart) => carts_out.push(cart),
+            Err(error) => return Err(error),
+        };
+    }
+
+    Ok(carts_out)
+}
+
+pub fn fetch_recipe_id_by_name(
+    name: &str,
+    connection: &mut SqliteConnection,
+) -> Result<Option<i32>, DieselError> {
+    recipes::table
+        .filter(recipes::normalized_name.eq(normalize_name(name)))
+        .select(recipes::id)
+        .first(connection)
+        .optional()
+}
+
+fn insert_archived_contents(
+    recipe_id: i32,
+    recipe: &ArchivedRecipe,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    let mut ingredient_ids = Vec::with_capacity(recipe.ingredients.len());
+
+    for ingredient in &recipe.ingredients {
+        match diesel::insert_into(ingredients::table)
+            .values((
+                ingredients::recipe_id.eq(recipe_id),
+                ingredients::preposition.eq(&ingredient.preposition),
+                ingredients::name.eq(&ingredient.name),
+                ingredients::quantity.eq(ingredient.quantity),
+                ingredients::unit.eq(&ingredient.unit),
+            ))
+            .returning(ingredients::id)
+            .get_result::<i32>(connection)
+        {
+            Ok(ingredient_id) => ingredient_ids.push(ingredient_id),
+            Err(error) => return Err(error),
+        };
+    }
+
+    for (position, step) in (0..).zip(&recipe.steps) {
+        let step_id: i32 = match diesel::insert_into(steps::table)
+            .values((
+                steps::recipe_id.eq(recipe_id),
+                steps::description.eq(&step.description),
+                steps::position.eq(position),
+                steps::duration_minutes.eq(step.duration_minutes),
+                steps::temperature_celsius.eq(step.temperature_celsius),
+                steps::passive.eq(step.passive),
+            ))
+            .returning(steps::id)
+            .get_result(connection)
+        {
+            Ok(step_id) => step_id,
+            Err(error) => return Err(error),
+        };
+
+        let step_ingredients: Vec<_> = step
+            .ingredients
+            .iter()
+            .filter_map(|index| ingredient_ids.get(*index))
+            .map(|ingredient_id| {
+                (
+                    step_ingredients::step_id.eq(step_id),
+                    step_ingredients::ingredient_id.eq(ingredient_id),
+                )
+            })
+            .collect();
+
+        match diesel::insert_into(step_ingredients::table)
+            .values(&step_ingredients)
+            .execute(connection)
+        {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+    }
+
+    match insert_recipe_tags(recipe_id, &recipe.tags, connection) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn insert_archived_recipe(
+    name: &str,
+    recipe: &ArchivedRecipe,
+    connection: &mut SqliteConnection,
+) -> Result<i32, DieselError> {
+    let name = name.trim();
+
+    let recipe_id: i32 = match diesel::insert_into(recipes::table)
+        .values((
+            recipes::name.eq(name),
+            recipes::normalized_name.eq(normalize_name(name)),
+            recipes::description.eq(&recipe.description),
+            recipes::prep_time_minutes.eq(recipe.prep_time_minutes),
+            recipes::cook_time_minutes.eq(recipe.cook_time_minutes),
+            recipes::difficulty.eq(&recipe.difficulty),
+            recipes::source_url.eq(&recipe.source_url),
+            recipes::notes.eq(&recipe.notes),
+        ))
+        .returning(recipes::id)
+        .get_result(connection)
+    {
+        Ok(recipe_id) => recipe_id,
+        Err(error) => return Err(error),
+    };
+
+    match insert_archived_contents(recipe_id, recipe, connection) {
+        Ok(()) => Ok(recipe_id),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn overwrite_recipe(
+    recipe_id: i32,
+    recipe: &ArchivedRecipe,
+    connection: &mut SqliteConnection,
+) -> Result<(), DieselError> {
+    match diesel::update(recipes::table.filter(recipes::id.eq(recipe_id)))
+        .set((
+            recipes::description.eq(&recipe.description),
+            recipes::prep_time_minutes.eq(recipe.prep_time_minutes),
+            recipes::cook_time_minutes.eq(recipe.cook_time_minutes),
+            recipes::difficulty.eq(&recipe.difficulty),
+            recipes::source_url.eq(&recipe.source_url),
+            recipes::notes.eq(&recipe.notes),
+        ))
+        .execute(connection)
+    {
+        Ok(_) => (),
+        Err(error) => return Err(error),
+    };
+
+    for result in [
+        diesel::delete(steps::table.filter(steps::recipe_id.eq(recipe_id))).execute(connection),
+        diesel::delete(ingredients::table.filter(ingredients::recipe_id.eq(recipe_id)))
+            .execute(connection),
+        diesel::delete(recipe_tags::table.filter(recipe_tags::recipe_id.eq(recipe_id)))
+            .execute(connection),
+    ] {
+        match result {
+            Ok(_) => (),
+            Err(error) => return Err(error),
+        };
+    }
+
+    insert_archived_contents(recipe_id, recipe, connection)
+}
+
+pub fn fetch_matching_cart_id(
+    created_at: PrimitiveDateTime,
+    recipe_ids: &[i32],
+    connection: &mut SqliteConnection,
+) -> Result<Option<i32>, DieselError> {
+    let cart_ids: Vec<i32> = match carts::table
+        .filter(carts::created_at.eq(created_at))
+        .select(carts::id)
+        .order_by(carts::id)
+        .load(connection)
+    {
+        Ok(cart_ids) => cart_ids,
+        Err(error) => return Err(error),
+    };
+
+    let mut expected_recipe_ids = recipe_ids.to_vec();
+    expected_recipe_ids.sort_unstable();
+    expected_recipe_ids.dedup();
+
+    for cart_id in cart_ids {
+        let cart_recipe_ids: Vec<i32> = match cart_recipes::table
+            .filter(cart_recipes::cart_id.eq(cart_id))
+            .select(cart_recipes::recipe_id)
+            .distinct()
+            .order_by(cart_recipes::recipe_id)
+            .load(connection)
+        {
+            Ok(cart_recipe_ids) => cart_recipe_ids,
+            Err(error) => return Err(error),
+        };
+
+        if cart_recipe_ids == expected_recipe_ids {
+            return Ok(Some(cart_id));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn insert_archived_cart(
+    created_at: PrimitiveDateTime,
+    recipe_ids: &[i32],
+    connection: &mut SqliteConnection,
+) -> Result<i32, DieselError> {
+    let cart_id: i32 = match diesel::insert_into(carts::table)
+        .values(carts::created_at.eq(created_at))
+        .returning(carts::id)
+        .get_result(connection)
+    {
+        Ok(cart_id) => cart_id,
+        Err(error) => return Err(error),
+    };
+
+    let cart_recipes: Vec<_> = recipe_ids
+        .iter()
+        .map(|recipe_id| {
+            (
+                cart_recipes::cart_id.eq(cart_id),
+                cart_recipes::recipe_id.eq(recipe_id),
+            )
+        })
+        .collect();
+
+    match diesel::insert_into(cart_recipes::table)
+        .values(&cart_recipes)
+        .execute(connection)
+    {
+        Ok(_) => Ok(cart_id),
+        Err(error) => Err(error),
+    }
+}
@@ -0,0 +1,133 @@
+use crate::ingredient_formatter;
+use crate::models::RecipeWithIngredientsOut;
+
+use serde_json::{json, Map, Value};
+
+#[must_use]
+pub fn format_duration(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("PT{minutes}M"),
+        (hours, 0) => format!("PT{hours}H"),
+        (hours, minutes) => format!("PT{hours}H{minutes}M"),
+    }
+}
+
+#[must_use]
+pub fn render(recipe: &RecipeWithIngredientsOut) -> Value {
+    let mut document = Map::new();
+
+    document.insert("@context".to_owned(), json!("https://schema.org"));
+    document.insert("@type".to_owned(), json!("Recipe"));
+    document.insert("name".to_owned(), json!(recipe.name));
+
+    if let Some(description) = &recipe.description {
+        document.insert("description".to_owned(), json!(description));
+    }
+
+    if let Some(prep_time_minutes) = recipe.prep_time_minutes {
+        document.insert(
+            "prepTime".to_owned(),
+            json!(format_duration(prep_time_minutes)),
+        );
+    }
+
+    if let Some(cook_time_minutes) = recipe.cook_time_minutes {
+        document.insert(
+            "cookTime".to_owned(),
+            json!(format_duration(cook_time_minutes)),
+        );
+    }
+
+    if let (Some(prep_time_minutes), Some(cook_time_minutes)) =
+        (recipe.prep_time_minutes, recipe.cook_time_minutes)
+    {
+        if let Some(total_time_minutes) = prep_time_minutes.checked_add(cook_time_minutes) {
+            document.insert(
+                "totalTime".to_owned(),
+                json!(format_duration(total_time_minutes)),
+            );
+        }
+    }
+
+    if let Some(source_url) = &recipe.source_url {
+        document.insert("isBasedOn".to_owned(), json!(source_url));
+    }
+
+    if !recipe.tags.is_empty() {
+        document.insert("keywords".to_owned(), json!(recipe.tags.join(", ")));
+    }
+
+    document.insert(
+        "recipeIngredient".to_owned(),
+        recipe
+            .ingredients
+            .iter()
+            .map(ingredient_formatter::format)
+            .collect::<Vec<String>>()
+            .into(),
+    );
+
+    document.insert(
+        "recipeInstructions".to_owned(),
+        recipe
+            .steps
+            .iter()
+            .map(|step| {
+                json!({
+                    "@type": "HowToStep",
+                    "position": step.position + 1,
+                    "text": step.description,
+                })
+            })
+            .collect::<Vec<Value>>()
+            .into(),
+    );
+
+    Value::Object(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, "PT0M")]
+    #[case(45, "PT45M")]
+    #[case(60, "PT1H")]
+    #[case(90, "PT1H30M")]
+    fn duration_formatting_test(#[case] minutes: i32, #[case] expected: &str) {
+        assert_eq!(format_duration(minutes), expected);
+    }
+
+    #[rstest]
+    #[case(Some(20), Some(40), Some("PT1H"))]
+    #[case(Some(20), None, None)]
+    #[case(Some(i32::MAX), Some(1), None)]
+    fn total_time_test(
+        #[case] prep_time_minutes: Option<i32>,
+        #[case] cook_time_minutes: Option<i32>,
+        #[case] expected: Option<&str>,
+    ) {
+        let recipe = RecipeWithIngredientsOut {
+            id: 1,
+            name: "Tapenade".to_owned(),
+            description: None,
+            prep_time_minutes,
+            cook_time_minutes,
+            difficulty: None,
+            source_url: None,
+            notes: None,
+            tags: vec![],
+            ingredients: vec![],
+            steps: vec![],
+            active_time_minutes: 0,
+            passive_time_minutes: 0,
+        };
+
+        assert_eq!(
+            render(&recipe).get("totalTime").and_then(Value::as_str),
+            expected
+        );
+    }
+}
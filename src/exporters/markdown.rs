@@ -0,0 +1,75 @@
+use super::summary;
+use crate::models::{CartWithRecipesOut, RecipeWithIngredientsOut};
+
+fn render_recipe(recipe: &RecipeWithIngredientsOut, level: usize, output: &mut Vec<String>) {
+    let heading = "#".repeat(level);
+    let subheading = "#".repeat(level + 1);
+
+    output.push(format!("{heading} {}", recipe.name));
+
+    if let Some(description) = &recipe.description {
+        output.push(description.clone());
+    }
+
+    if let Some(summary) = summary(recipe) {
+        output.push(format!("*{summary}*"));
+    }
+
+    if !recipe.tags.is_empty() {
+        output.push(format!("Tags : {}", recipe.tags.join(", ")));
+    }
+
+    output.push(format!("{subheading} Ingr\u{e9}dients"));
+    output.push(
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| format!("- {}", ingredient.display))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    );
+
+    output.push(format!("{subheading} \u{c9}tapes"));
+    output.push(
+        recipe
+            .steps
+            .iter()
+            .zip(1..)
+            .map(|(step, number)| format!("{number}. {}", step.description))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    );
+
+    if let Some(notes) = &recipe.notes {
+        output.push(format!("{subheading} Notes"));
+        output.push(notes.clone());
+    }
+
+    if let Some(source_url) = &recipe.source_url {
+        output.push(format!("Source : <{source_url}>"));
+    }
+}
+
+#[must_use]
+pub fn render(recipe: &RecipeWithIngredientsOut) -> String {
+    let mut output = Vec::new();
+
+    render_recipe(recipe, 1, &mut output);
+
+    output.join("\n\n") + "\n"
+}
+
+#[must_use]
+pub fn render_cart(cart: &CartWithRecipesOut) -> String {
+    let mut output = vec![format!("# Panier {}", cart.id)];
+
+    if cart.recipes.is_empty() {
+        output.push("*Aucune recette dans ce panier.*".to_owned());
+    }
+
+    for recipe in &cart.recipes {
+        render_recipe(recipe, 2, &mut output);
+    }
+
+    output.join("\n\n") + "\n"
+}
@@ -0,0 +1,40 @@
+pub mod jsonld;
+pub mod markdown;
+pub mod pdf;
+pub mod plain_text;
+
+use crate::models::RecipeWithIngredientsOut;
+
+#[must_use]
+pub fn format_minutes(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes} min"),
+        (hours, 0) => format!("{hours} h"),
+        (hours, minutes) => format!("{hours} h {minutes:02}"),
+    }
+}
+
+fn summary(recipe: &RecipeWithIngredientsOut) -> Option<String> {
+    let mut parts = Vec::new();
+
+    if let Some(prep_time_minutes) = recipe.prep_time_minutes {
+        parts.push(format!(
+            "Pr\u{e9}paration : {}",
+            format_minutes(prep_time_minutes)
+        ));
+    }
+
+    if let Some(cook_time_minutes) = recipe.cook_time_minutes {
+        parts.push(format!("Cuisson : {}", format_minutes(cook_time_minutes)));
+    }
+
+    if let Some(difficulty) = &recipe.difficulty {
+        parts.push(format!("Difficult\u{e9} : {difficulty}"));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" \u{b7} "))
+    }
+}
@@ -0,0 +1,384 @@
+use super::summary;
+use crate::models::{CartWithRecipesOut, RecipeWithIngredientsOut};
+use crate::shopping_list;
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const LINE_SPACING: f32 = 1.35;
+const LIST_INDENT: f32 = 18.0;
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+impl Font {
+    const ALL: [Self; 3] = [Self::Regular, Self::Bold, Self::Italic];
+
+    const fn resource_name(self) -> Name<'static> {
+        match self {
+            Self::Regular => Name(b"F1"),
+            Self::Bold => Name(b"F2"),
+            Self::Italic => Name(b"F3"),
+        }
+    }
+
+    const fn base_font(self) -> Name<'static> {
+        match self {
+            Self::Regular => Name(b"Helvetica"),
+            Self::Bold => Name(b"Helvetica-Bold"),
+            Self::Italic => Name(b"Helvetica-Oblique"),
+        }
+    }
+}
+
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7e}' => c as u8,
+            '\u{a0}'..='\u{ff}' => u8::try_from(u32::from(c)).unwrap_or(b'?'),
+            '\u{20ac}' => 0x80,
+            '\u{2026}' => 0x85,
+            '\u{152}' => 0x8c,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{153}' => 0x9c,
+            '\t' | '\n' | '\r' => b' ',
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let units: f32 = text
+        .chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '\'' | '.' | ',' | ';' | ':' | '!' | '|' => 250.0,
+            ' ' | 'f' | 't' | 'I' | '(' | ')' | '-' => 300.0,
+            'r' => 340.0,
+            'm' | 'w' | 'M' | 'W' | '\u{153}' => 850.0,
+            'A'..='Z' | '\u{c0}'..='\u{de}' => 690.0,
+            _ => 560.0,
+        })
+        .sum();
+
+    let width = units * size / 1000.0;
+
+    match font {
+        Font::Bold => width * 1.06,
+        Font::Regular | Font::Italic => width,
+    }
+}
+
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_owned()
+        } else {
+            format!("{line} {word}")
+        };
+
+        if line.is_empty() || text_width(&candidate, font, size) <= width {
+            line = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_owned()));
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+struct Layout {
+    pages: Vec<Content>,
+    content: Content,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            content: Content::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn new_page(&mut self) {
+        let content = std::mem::replace(&mut self.content, Content::new());
+
+        self.pages.push(content);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn next_line(&mut self, height: f32) -> f32 {
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+
+        self.y -= height;
+        self.y
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn show(&mut self, text: &str, font: Font, size: f32, x: f32, y: f32) {
+        self.content
+            .begin_text()
+            .set_font(font.resource_name(), size)
+            .next_line(x, y)
+            .show(Str(&encode(text)))
+            .end_text();
+    }
+
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        let x = MARGIN + indent;
+
+        for line in wrap(text, font, size, PAGE_WIDTH - MARGIN - x) {
+            let y = self.next_line(size * LINE_SPACING);
+            self.show(&line, font, size, x, y);
+        }
+    }
+
+    fn heading(&mut self, text: &str, size: f32) {
+        self.space(size * 0.6);
+        self.paragraph(text, Font::Bold, size, 0.0);
+        self.space(size * 0.2);
+    }
+
+    fn list_item(&mut self, marker: &str, text: &str, size: f32) {
+        let x = MARGIN + LIST_INDENT;
+
+        for (index, line) in wrap(text, Font::Regular, size, PAGE_WIDTH - MARGIN - x)
+            .iter()
+            .enumerate()
+        {
+            let y = self.next_line(size * LINE_SPACING);
+
+            if index == 0 {
+                self.show(marker, Font::Regular, size, MARGIN, y);
+            }
+
+            self.show(line, Font::Regular, size, x, y);
+        }
+    }
+
+    fn checkbox_item(&mut self, text: &str, size: f32) {
+        let x = MARGIN + LIST_INDENT;
+
+        for (index, line) in wrap(text, Font::Regular, size, PAGE_WIDTH - MARGIN - x)
+            .iter()
+            .enumerate()
+        {
+            let y = self.next_line(size * LINE_SPACING * 1.15);
+
+            if index == 0 {
+                self.content
+                    .set_line_width(0.8)
+                    .rect(MARGIN + 2.0, y - 1.0, size * 0.8, size * 0.8)
+                    .stroke();
+            }
+
+            self.show(line, Font::Regular, size, x, y);
+        }
+    }
+
+    fn finish(mut self, title: &str) -> Vec<u8> {
+        self.new_page();
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let info_id = Ref::new(3);
+        let font_ids: Vec<Ref> = (4..).take(Font::ALL.len()).map(Ref::new).collect();
+        let first_page_id = 4 + i32::try_from(Font::ALL.len()).unwrap_or_default();
+
+        let page_ids: Vec<(Ref, Ref)> = (0..)
+            .take(self.pages.len())
+            .map(|index| {
+                (
+                    Ref::new(first_page_id + 2 * index),
+                    Ref::new(first_page_id + 2 * index + 1),
+                )
+            })
+            .collect();
+
+        let mut pdf = Pdf::new();
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+            .count(i32::try_from(page_ids.len()).unwrap_or_default());
+        pdf.document_info(info_id).title(TextStr(title));
+
+        for (font, font_id) in Font::ALL.iter().zip(&font_ids) {
+            pdf.type1_font(*font_id)
+                .base_font(font.base_font())
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+
+        for ((page_id, content_id), content) in page_ids.into_iter().zip(self.pages) {
+            let mut page = pdf.page(page_id);
+
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+
+            let mut resources = page.resources();
+            let mut fonts = resources.fonts();
+
+            for (font, font_id) in Font::ALL.iter().zip(&font_ids) {
+                fonts.pair(font.resource_name(), *font_id);
+            }
+
+            fonts.finish();
+            resources.finish();
+            page.finish();
+
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+#[must_use]
+pub fn render_recipe(recipe: &RecipeWithIngredientsOut) -> Vec<u8> {
+    let mut layout = Layout::new();
+
+    layout.paragraph(&recipe.name, Font::Bold, 22.0, 0.0);
+
+    if let Some(description) = &recipe.description {
+        layout.space(4.0);
+        layout.paragraph(description, Font::Italic, 11.0, 0.0);
+    }
+
+    if let Some(summary) = summary(recipe) {
+        layout.space(4.0);
+        layout.paragraph(&summary, Font::Regular, 10.0, 0.0);
+    }
+
+    if !recipe.tags.is_empty() {
+        layout.paragraph(
+            &format!("Tags : {}", recipe.tags.join(", ")),
+            Font::Regular,
+            10.0,
+            0.0,
+        );
+    }
+
+    layout.heading("Ingr\u{e9}dients", 15.0);
+
+    for ingredient in &recipe.ingredients {
+        layout.list_item("\u{2022}", &ingredient.display, 11.0);
+    }
+
+    layout.heading("\u{c9}tapes", 15.0);
+
+    for (step, number) in recipe.steps.iter().zip(1..) {
+        layout.list_item(&format!("{number}."), &step.description, 11.0);
+        layout.space(3.0);
+    }
+
+    if let Some(notes) = &recipe.notes {
+        layout.heading("Notes", 15.0);
+        layout.paragraph(notes, Font::Regular, 11.0, 0.0);
+    }
+
+    if let Some(source_url) = &recipe.source_url {
+        layout.space(12.0);
+        layout.paragraph(&format!("Source : {source_url}"), Font::Italic, 9.0, 0.0);
+    }
+
+    layout.finish(&recipe.name)
+}
+
+#[must_use]
+pub fn render_shopping_list(cart: &CartWithRecipesOut) -> Vec<u8> {
+    let shopping_list = shopping_list::build(cart);
+    let title = format!("Liste de courses \u{2013} panier {}", cart.id);
+
+    let mut layout = Layout::new();
+
+    layout.paragraph(&title, Font::Bold, 22.0, 0.0);
+
+    if cart.recipes.is_empty() {
+        layout.space(4.0);
+        layout.paragraph("Aucune recette dans ce panier.", Font::Italic, 11.0, 0.0);
+    } else {
+        let recipe_names: Vec<&str> = cart
+            .recipes
+            .iter()
+            .map(|recipe| recipe.name.as_str())
+            .collect();
+
+        layout.space(4.0);
+        layout.paragraph(
+            &format!("Pour : {}", recipe_names.join(", ")),
+            Font::Italic,
+            11.0,
+            0.0,
+        );
+    }
+
+    for aisle in &shopping_list.aisles {
+        layout.heading(&aisle.name, 15.0);
+
+        for item in &aisle.items {
+            layout.checkbox_item(&item.display, 11.0);
+        }
+    }
+
+    layout.finish(&title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Cr\u{e8}me br\u{fb}l\u{e9}e", b"Cr\xe8me br\xfbl\xe9e".to_vec())]
+    #[case("C\u{153}ur d\u{2019}artichaut", b"C\x9cur d\x92artichaut".to_vec())]
+    #[case("\u{2022} 5 \u{20ac}", b"\x95 5 \x80".to_vec())]
+    #[case("\u{1f37d}", b"?".to_vec())]
+    fn win_ansi_encoding_test(#[case] test_input: &str, #[case] expected: Vec<u8>) {
+        assert_eq!(encode(test_input), expected);
+    }
+
+    #[test]
+    fn wrap_test() {
+        let lines = wrap(
+            "Faire revenir les oignons dans le beurre pendant 10 minutes",
+            Font::Regular,
+            10.0,
+            120.0,
+        );
+
+        assert!(lines.len() > 1);
+        assert!(lines
+            .iter()
+            .all(|line| text_width(line, Font::Regular, 10.0) <= 120.0));
+        assert_eq!(
+            lines.join(" "),
+            "Faire revenir les oignons dans le beurre pendant 10 minutes"
+        );
+    }
+}
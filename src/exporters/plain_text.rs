@@ -0,0 +1,54 @@
+use super::summary;
+use crate::models::RecipeWithIngredientsOut;
+
+fn underline(title: &str, character: char) -> String {
+    format!(
+        "{title}\n{}",
+        character.to_string().repeat(title.chars().count())
+    )
+}
+
+#[must_use]
+pub fn render(recipe: &RecipeWithIngredientsOut) -> String {
+    let mut output = vec![underline(&recipe.name, '=')];
+
+    if let Some(description) = &recipe.description {
+        output.push(description.clone());
+    }
+
+    if let Some(summary) = summary(recipe) {
+        output.push(summary);
+    }
+
+    output.push(underline("Ingr\u{e9}dients", '-'));
+    output.push(
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| format!("  * {}", ingredient.display))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    );
+
+    output.push(underline("\u{c9}tapes", '-'));
+    output.push(
+        recipe
+            .steps
+            .iter()
+            .zip(1..)
+            .map(|(step, number)| format!("  {number}. {}", step.description))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    );
+
+    if let Some(notes) = &recipe.notes {
+        output.push(underline("Notes", '-'));
+        output.push(notes.clone());
+    }
+
+    if let Some(source_url) = &recipe.source_url {
+        output.push(format!("Source : {source_url}"));
+    }
+
+    output.join("\n\n") + "\n"
+}
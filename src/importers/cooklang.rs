@@ -0,0 +1,313 @@
+use super::{clean_text, collect_tags, parse_difficulty, parse_minutes, ImportError};
+use crate::ingredient_formatter;
+use crate::models::RecipeIn;
+use crate::text::fold;
+
+const SIGILS: [char; 3] = ['@', '#', '~'];
+const NAME_TERMINATORS: [char; 6] = ['.', ',', ';', ':', '!', '?'];
+
+struct Ingredient {
+    name: String,
+    quantity: Option<f32>,
+    unit: String,
+}
+
+impl Ingredient {
+    fn line(&self) -> String {
+        self.quantity.map_or_else(
+            || self.name.clone(),
+            |quantity| ingredient_formatter::format_parts(&self.name, quantity, &self.unit),
+        )
+    }
+}
+
+fn metadata_entry(line: &str) -> Option<(String, String)> {
+    line.split_once(':')
+        .map(|(key, value)| (fold(key.trim()), value.trim().to_owned()))
+}
+
+fn metadata_value<'a>(metadata: &'a [(String, String)], keys: &[&str]) -> Option<&'a str> {
+    metadata
+        .iter()
+        .find(|(key, value)| keys.contains(&key.as_str()) && !value.is_empty())
+        .map(|(_, value)| value.as_str())
+}
+
+fn split_front_matter(document: &str) -> (Vec<(String, String)>, &str) {
+    let Some(rest) = document.trim_start().strip_prefix("---") else {
+        return (Vec::new(), document);
+    };
+
+    let Some(end) = rest.find("\n---") else {
+        return (Vec::new(), document);
+    };
+
+    let body = rest[end + 4..]
+        .split_once('\n')
+        .map_or("", |(_, body)| body);
+
+    (
+        rest[..end].lines().filter_map(metadata_entry).collect(),
+        body,
+    )
+}
+
+fn strip_comments(document: &str) -> String {
+    let mut stripped = String::with_capacity(document.len());
+    let mut rest = document;
+
+    while let Some(start) = rest.find("[-") {
+        stripped.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("-]")
+            .map_or("", |end| &rest[start + end + 2..]);
+    }
+
+    stripped.push_str(rest);
+
+    stripped
+        .lines()
+        .map(|line| line.find("--").map_or(line, |start| &line[..start]))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+fn parse_quantity(text: &str) -> Option<f32> {
+    let text = text.trim().replace(',', ".");
+
+    match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator = numerator.trim().parse::<f32>().ok()?;
+            let denominator = denominator.trim().parse::<f32>().ok()?;
+
+            (denominator > 0.0).then(|| numerator / denominator)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+fn component(text: &str) -> (&str, Option<&str>, &str) {
+    if let Some(brace) = text.find('{') {
+        let name = &text[..brace];
+
+        if !name.contains(SIGILS) && !name.contains(NAME_TERMINATORS) && !name.contains('\n') {
+            if let Some(close) = text[brace..].find('}') {
+                return (
+                    name.trim(),
+                    Some(&text[brace + 1..brace + close]),
+                    &text[brace + close + 1..],
+                );
+            }
+        }
+    }
+
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(text.len());
+
+    (&text[..end], None, &text[end..])
+}
+
+fn add_ingredient(ingredients: &mut Vec<Ingredient>, name: &str, amount: Option<&str>) {
+    let (quantity, unit) = amount.map_or(("", ""), |amount| {
+        amount.split_once('%').unwrap_or((amount, ""))
+    });
+
+    let ingredient = Ingredient {
+        name: clean_text(name),
+        quantity: parse_quantity(quantity),
+        unit: clean_text(unit),
+    };
+
+    let existing = ingredients.iter_mut().find(|other| {
+        fold(&other.name) == fold(&ingredient.name) && fold(&other.unit) == fold(&ingredient.unit)
+    });
+
+    match existing {
+        Some(existing) => {
+            if let (Some(total), Some(quantity)) = (&mut existing.quantity, ingredient.quantity) {
+                *total += quantity;
+            } else if ingredient.quantity.is_some() {
+                ingredients.push(ingredient);
+            }
+        }
+        None => ingredients.push(ingredient),
+    }
+}
+
+fn parse_step(text: &str, ingredients: &mut Vec<Ingredient>) -> String {
+    let mut step = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(SIGILS) {
+        step.push_str(&rest[..start]);
+
+        let sigil = &rest[start..=start];
+        let (name, amount, remainder) = component(&rest[start + 1..]);
+
+        rest = remainder;
+
+        if name.is_empty() && amount.is_none() {
+            step.push_str(sigil);
+            continue;
+        }
+
+        match sigil {
+            "@" => {
+                add_ingredient(ingredients, name, amount);
+                step.push_str(name);
+            }
+            "~" => match amount.filter(|amount| !amount.is_empty()) {
+                Some(amount) => step.push_str(&amount.replace('%', " ")),
+                None => step.push_str(name),
+            },
+            _ => step.push_str(name),
+        }
+    }
+
+    step.push_str(rest);
+
+    clean_text(&step)
+}
+
+pub fn parse(document: &str, default_name: Option<&str>) -> Result<RecipeIn, ImportError> {
+    let (mut metadata, body) = split_front_matter(document);
+    let body = strip_comments(body);
+
+    let mut ingredients = Vec::new();
+    let mut steps = Vec::new();
+    let mut notes = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in body.lines().map(str::trim).chain(std::iter::once("")) {
+        if let Some(entry) = line.strip_prefix(">>") {
+            metadata.extend(metadata_entry(entry));
+        } else if let Some(note) = line.strip_prefix('>') {
+            notes.push(clean_text(note));
+        } else if line.is_empty() || line.starts_with('=') {
+            if !paragraph.is_empty() {
+                steps.push(parse_step(&paragraph.join(" "), &mut ingredients));
+                paragraph.clear();
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    let Some(name) = metadata_value(&metadata, &["title"])
+        .or(default_name)
+        .map(clean_text)
+    else {
+        return Err(ImportError::MissingField("name"));
+    };
+
+    if ingredients.is_empty() {
+        return Err(ImportError::MissingField("ingredients"));
+    }
+
+    let ingredients = ingredients.iter().map(Ingredient::line).collect();
+
+    let time = |keys: &[&str]| metadata_value(&metadata, keys).and_then(parse_minutes);
+
+    Ok(RecipeIn {
+        name,
+        ingredients,
+        steps,
+        description: metadata_value(&metadata, &["description", "introduction"]).map(clean_text),
+        prep_time_minutes: time(&["prep time", "prep_time", "time.prep"]),
+        cook_time_minutes: time(&["cook time", "cook_time", "time.cook"]),
+        difficulty: metadata_value(&metadata, &["difficulty"]).and_then(parse_difficulty),
+        source_url: metadata_value(&metadata, &["source", "source.url", "url"])
+            .filter(|url| url.starts_with("http"))
+            .map(str::to_owned),
+        notes: Some(notes.join("\n")).filter(|notes| !notes.trim().is_empty()),
+        tags: collect_tags(
+            metadata_value(&metadata, &["tags"])
+                .unwrap_or_default()
+                .trim_matches(['[', ']'])
+                .split(',')
+                .map(|tag| tag.trim().trim_matches(['"', '\'']).to_owned()),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_cooklang_test() {
+        let document = ">> title: Gratin de gnocchi\n>> source: https://www.example.com/gratin\n>> prep time: 15 minutes\n>> tags: gratin, Hiver\n\n-- Les gnocchi frais sont meilleurs\nFaire cuire les @gnocchi{400%g} dans une #grande casserole{} d'eau sal\u{e9}e.\n\nFaire fondre le @beurre{30%g}, ajouter la @farine{30%g} puis le @lait{0,5%litre}.\nSaler avec du @sel. [- \u{e0} volont\u{e9} -]\n\nEnfourner pour ~{20%minutes} et ajouter le @beurre{10%g}.\n\n> Servir aussit\u{f4}t.\n";
+
+        let recipe = parse(document, None).unwrap();
+
+        assert_eq!(recipe.name, "Gratin de gnocchi");
+        assert_eq!(
+            recipe.source_url.as_deref(),
+            Some("https://www.example.com/gratin")
+        );
+        assert_eq!(recipe.prep_time_minutes, Some(15));
+        assert_eq!(recipe.tags, vec!["gratin", "hiver"]);
+        assert_eq!(
+            recipe.ingredients,
+            vec![
+                "400 g de gnocchi",
+                "40 g de beurre",
+                "30 g de farine",
+                "0,5 litre de lait",
+                "sel"
+            ]
+        );
+        assert_eq!(
+            recipe.steps,
+            vec![
+                "Faire cuire les gnocchi dans une grande casserole d'eau sal\u{e9}e.",
+                "Faire fondre le beurre, ajouter la farine puis le lait. Saler avec du sel.",
+                "Enfourner pour 20 minutes et ajouter le beurre."
+            ]
+        );
+        assert_eq!(recipe.notes.as_deref(), Some("Servir aussit\u{f4}t."));
+    }
+
+    #[test]
+    fn parse_front_matter_test() {
+        let document = "---\ntitle: Tapenade\ndifficulty: easy\ntags: [ap\u{e9}ritif, \"olive\"]\n---\nMixer les @olives noires{200%g} avec les @c\u{e2}pres{8}.\n";
+
+        let recipe = parse(document, None).unwrap();
+
+        assert_eq!(recipe.name, "Tapenade");
+        assert_eq!(recipe.difficulty.as_deref(), Some("facile"));
+        assert_eq!(recipe.tags, vec!["ap\u{e9}ritif", "olive"]);
+        assert_eq!(
+            recipe.ingredients,
+            vec!["200 g d'olives noires", "8 c\u{e2}pres"]
+        );
+        assert_eq!(
+            recipe.steps,
+            vec!["Mixer les olives noires avec les c\u{e2}pres."]
+        );
+    }
+
+    #[rstest]
+    #[case("Mixer les @olives{200%g}.", None, ImportError::MissingField("name"))]
+    #[case(
+        ">> title: Tapenade\n\nMixer.",
+        None,
+        ImportError::MissingField("ingredients")
+    )]
+    fn parse_error_test(
+        #[case] document: &str,
+        #[case] default_name: Option<&str>,
+        #[case] expected: ImportError,
+    ) {
+        assert_eq!(parse(document, default_name).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn parse_default_name_test() {
+        let recipe = parse("Mixer les @olives{200%g}.", Some("tapenade")).unwrap();
+
+        assert_eq!(recipe.name, "tapenade");
+    }
+}
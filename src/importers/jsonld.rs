@@ -0,0 +1,226 @@
+use super::{clean_text, collect_tags, split_ingredients, texts, ImportError};
+use crate::models::RecipeIn;
+
+use regex::Regex;
+use serde_json::Value;
+
+fn is_recipe(value: &Value) -> bool {
+    let is_recipe_type = |value: &Value| {
+        value
+            .as_str()
+            .is_some_and(|recipe_type| recipe_type == "Recipe" || recipe_type.ends_with("/Recipe"))
+    };
+
+    match value.get("@type") {
+        Some(Value::Array(types)) => types.iter().any(is_recipe_type),
+        Some(recipe_type) => is_recipe_type(recipe_type),
+        None => false,
+    }
+}
+
+fn find_recipe(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_recipe),
+        Value::Object(object) => {
+            if is_recipe(value) {
+                return Some(value);
+            }
+
+            ["@graph", "mainEntity"]
+                .iter()
+                .find_map(|key| object.get(*key).and_then(find_recipe))
+        }
+        _ => None,
+    }
+}
+
+fn extract_scripts(html: &str) -> Result<Vec<&str>, ImportError> {
+    let Ok(re) = Regex::new(
+        r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#,
+    ) else {
+        return Err(ImportError::InvalidDocument(
+            "invalid script regex".to_owned(),
+        ));
+    };
+
+    Ok(re
+        .captures_iter(html)
+        .filter_map(|caps| caps.get(1).map(|script| script.as_str()))
+        .collect())
+}
+
+fn collect_instructions(value: &Value, steps: &mut Vec<String>) {
+    match value {
+        Value::String(text) => {
+            steps.extend(text.lines().map(clean_text).filter(|step| !step.is_empty()));
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_instructions(item, steps);
+            }
+        }
+        Value::Object(object) => {
+            if let Some(items) = object.get("itemListElement") {
+                collect_instructions(items, steps);
+            } else if let Some(text) = object.get("text").or_else(|| object.get("name")) {
+                collect_instructions(text, steps);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[must_use]
+pub fn parse_duration(duration: &str) -> Option<i32> {
+    let re =
+        Regex::new(r"^P(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+)(?:[.,]\d+)?S)?)?$").ok()?;
+
+    let caps = re.captures(duration.trim())?;
+
+    let component = |index: usize| -> i32 {
+        caps.get(index)
+            .and_then(|res| res.as_str().parse::<i32>().ok())
+            .unwrap_or(0)
+    };
+
+    let days = component(1).checked_mul(24 * 60)?;
+    let hours = component(2).checked_mul(60)?;
+    let seconds = component(4) / 60 + i32::from(component(4) % 60 != 0);
+
+    days.checked_add(hours)?
+        .checked_add(component(3))?
+        .checked_add(seconds)
+}
+
+fn map_recipe(recipe: &Value) -> Result<RecipeIn, ImportError> {
+    let Some(name) = texts(recipe.get("name")).into_iter().next() else {
+        return Err(ImportError::MissingField("name"));
+    };
+
+    let (ingredients, notes) = split_ingredients(texts(
+        recipe
+            .get("recipeIngredient")
+            .or_else(|| recipe.get("ingredients")),
+    ));
+
+    let mut steps = Vec::new();
+
+    if let Some(instructions) = recipe.get("recipeInstructions") {
+        collect_instructions(instructions, &mut steps);
+    }
+
+    let tags = collect_tags(
+        texts(recipe.get("keywords"))
+            .iter()
+            .flat_map(|keywords| keywords.split(','))
+            .map(str::to_owned)
+            .chain(texts(recipe.get("recipeCategory")))
+            .chain(texts(recipe.get("recipeCuisine"))),
+    );
+
+    Ok(RecipeIn {
+        name,
+        ingredients,
+        steps,
+        description: texts(recipe.get("description")).into_iter().next(),
+        prep_time_minutes: recipe
+            .get("prepTime")
+            .and_then(Value::as_str)
+            .and_then(parse_duration),
+        cook_time_minutes: recipe
+            .get("cookTime")
+            .and_then(Value::as_str)
+            .and_then(parse_duration),
+        difficulty: None,
+        source_url: texts(recipe.get("url").or_else(|| recipe.get("mainEntityOfPage")))
+            .into_iter()
+            .find(|url| url.starts_with("http")),
+        notes,
+        tags,
+    })
+}
+
+pub fn parse(document: &str) -> Result<RecipeIn, ImportError> {
+    let candidates = if document.trim_start().starts_with('<') {
+        match extract_scripts(document) {
+            Ok(scripts) => scripts,
+            Err(error) => return Err(error),
+        }
+    } else {
+        vec![document]
+    };
+
+    let mut json_error = None;
+
+    for candidate in candidates {
+        match serde_json::from_str::<Value>(candidate) {
+            Ok(value) => {
+                if let Some(recipe) = find_recipe(&value) {
+                    return map_recipe(recipe);
+                }
+            }
+            Err(error) => json_error = Some(error),
+        }
+    }
+
+    Err(json_error.map_or(ImportError::NoRecipeFound(), |error| {
+        ImportError::InvalidDocument(error.to_string())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("PT15M", Some(15))]
+    #[case("PT1H30M", Some(90))]
+    #[case("P0DT0H20M", Some(20))]
+    #[case("PT2H", Some(120))]
+    #[case("PT90S", Some(2))]
+    #[case("P1D", Some(1440))]
+    #[case("20 minutes", None)]
+    #[case("P9999999D", None)]
+    #[case("PT99999999H", None)]
+    #[case("PT2147483647S", Some(35_791_395))]
+    fn duration_parsing_test(#[case] test_input: &str, #[case] expected: Option<i32>) {
+        assert_eq!(parse_duration(test_input), expected);
+    }
+
+    #[test]
+    fn instructions_parsing_test() {
+        let instructions = serde_json::json!([
+            {"@type": "HowToSection", "name": "P\u{e2}te", "itemListElement": [
+                {"@type": "HowToStep", "text": "M\u{e9}langer la farine et le beurre."},
+                {"@type": "HowToStep", "text": "Laisser reposer 30 minutes."}
+            ]},
+            "Etaler la p\u{e2}te.\nEnfourner."
+        ]);
+
+        let mut steps = Vec::new();
+        collect_instructions(&instructions, &mut steps);
+
+        assert_eq!(
+            steps,
+            vec![
+                "M\u{e9}langer la farine et le beurre.",
+                "Laisser reposer 30 minutes.",
+                "Etaler la p\u{e2}te.",
+                "Enfourner."
+            ]
+        );
+    }
+
+    #[test]
+    fn no_recipe_found_test() {
+        assert_eq!(
+            parse(r#"{"@context": "https://schema.org", "@type": "WebPage"}"#),
+            Err(ImportError::NoRecipeFound())
+        );
+        assert_eq!(
+            parse("<html><body>Pas de recette</body></html>"),
+            Err(ImportError::NoRecipeFound())
+        );
+    }
+}
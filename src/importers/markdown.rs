@@ -0,0 +1,178 @@
+use super::{clean_text, join_notes, split_ingredients, strip_step_number, ImportError};
+use crate::models::RecipeIn;
+use crate::text::fold;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Description,
+    Ingredients,
+    Steps,
+    Other,
+}
+
+fn heading_text(line: &str) -> &str {
+    line.trim_start_matches('#')
+        .trim()
+        .trim_matches('*')
+        .trim_end_matches(':')
+        .trim()
+}
+
+fn section(line: &str) -> Option<Section> {
+    let folded = fold(heading_text(line));
+
+    if folded.starts_with("ingredients") {
+        Some(Section::Ingredients)
+    } else if ["preparation", "etapes", "instructions"]
+        .iter()
+        .any(|name| folded.starts_with(name))
+    {
+        Some(Section::Steps)
+    } else if line.starts_with('#') {
+        Some(Section::Other)
+    } else {
+        None
+    }
+}
+
+fn is_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    line.strip_prefix(['-', '*', '+', '\u{2022}'])
+        .filter(|item| item.starts_with(' '))
+        .map(str::trim_start)
+}
+
+pub fn parse(document: &str) -> Result<RecipeIn, ImportError> {
+    let mut lines = document
+        .lines()
+        .map(str::trim)
+        .filter(|line| !is_underline(line))
+        .skip_while(|line| line.is_empty());
+
+    let Some(name) = lines.next().map(|line| clean_text(heading_text(line))) else {
+        return Err(ImportError::MissingField("name"));
+    };
+
+    let mut current = Section::Description;
+    let mut description = Vec::new();
+    let mut ingredients = Vec::new();
+    let mut steps: Vec<String> = Vec::new();
+    let mut other = Vec::new();
+    let mut step_open = false;
+
+    for line in lines {
+        if let Some(next) = section(line) {
+            current = next;
+            step_open = false;
+
+            if next == Section::Other {
+                other.push(heading_text(line).to_owned());
+            }
+
+            continue;
+        }
+
+        if line.is_empty() {
+            step_open = false;
+            continue;
+        }
+
+        match current {
+            Section::Description => description.push(line),
+            Section::Ingredients => {
+                ingredients.push(clean_text(list_item(line).unwrap_or(line)));
+            }
+            Section::Steps => match (
+                strip_step_number(line).or_else(|| list_item(line)),
+                steps.last_mut(),
+            ) {
+                (None, Some(step)) if step_open => {
+                    step.push(' ');
+                    step.push_str(&clean_text(line));
+                }
+                (item, _) => {
+                    steps.push(clean_text(item.unwrap_or(line)));
+                    step_open = true;
+                }
+            },
+            Section::Other => other.push(line.to_owned()),
+        }
+    }
+
+    if ingredients.is_empty() {
+        return Err(ImportError::MissingField("ingredients"));
+    }
+
+    if steps.is_empty() {
+        return Err(ImportError::MissingField("steps"));
+    }
+
+    let (ingredients, unparsed_note) = split_ingredients(ingredients);
+
+    Ok(RecipeIn {
+        name,
+        ingredients,
+        steps,
+        description: (!description.is_empty()).then(|| clean_text(&description.join(" "))),
+        notes: join_notes([unparsed_note, Some(other.join("\n"))]),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_markdown_test() {
+        let document = "# Tapenade\n\nLa meilleure recette.\n\n## Ingr\u{e9}dients\n\n- 200 g d'olives noires\n- 8 c\u{e2}pres\n- sel\n\n## Pr\u{e9}paration\n\n1. Hacher l'ail.\n2. Mixer les olives\n   avec les c\u{e2}pres.\n\n## Astuce\n\nServir frais.\n";
+
+        let recipe = parse(document).unwrap();
+
+        assert_eq!(recipe.name, "Tapenade");
+        assert_eq!(recipe.description.as_deref(), Some("La meilleure recette."));
+        assert_eq!(
+            recipe.ingredients,
+            vec!["200 g d'olives noires", "8 c\u{e2}pres"]
+        );
+        assert_eq!(
+            recipe.steps,
+            vec!["Hacher l'ail.", "Mixer les olives avec les c\u{e2}pres."]
+        );
+        assert_eq!(
+            recipe.notes.as_deref(),
+            Some("Ingr\u{e9}dients sans quantit\u{e9} : sel\n\nAstuce\nServir frais.")
+        );
+    }
+
+    #[test]
+    fn parse_plain_text_test() {
+        let document = "Tapenade\n========\n\nIngr\u{e9}dients :\n200 g d'olives noires\n8 c\u{e2}pres\n\nPr\u{e9}paration :\n1) Hacher l'ail.\n2) Mixer.\n";
+
+        let recipe = parse(document).unwrap();
+
+        assert_eq!(recipe.name, "Tapenade");
+        assert_eq!(recipe.description, None);
+        assert_eq!(recipe.ingredients.len(), 2);
+        assert_eq!(recipe.steps, vec!["Hacher l'ail.", "Mixer."]);
+        assert_eq!(recipe.notes, None);
+    }
+
+    #[rstest]
+    #[case("", ImportError::MissingField("name"))]
+    #[case(
+        "# Tapenade\n\n## Pr\u{e9}paration\n\n1. Mixer.\n",
+        ImportError::MissingField("ingredients")
+    )]
+    #[case(
+        "# Tapenade\n\n## Ingr\u{e9}dients\n\n- 8 c\u{e2}pres\n",
+        ImportError::MissingField("steps")
+    )]
+    fn parse_error_test(#[case] document: &str, #[case] expected: ImportError) {
+        assert_eq!(parse(document).unwrap_err(), expected);
+    }
+}
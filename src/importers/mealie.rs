@@ -0,0 +1,205 @@
+use super::{clean_text, collect_tags, parse_minutes, texts, ImportError};
+use crate::ingredient_formatter;
+use crate::models::RecipeIn;
+
+use serde_json::Value;
+
+fn field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(clean_text)
+        .filter(|text| !text.is_empty())
+}
+
+fn ingredient_line(ingredient: &Value) -> Option<String> {
+    if let Some(line) = ingredient.as_str() {
+        return Some(clean_text(line));
+    }
+
+    let structured = texts(ingredient.get("food"))
+        .into_iter()
+        .next()
+        .map(|food| {
+            let quantity = ingredient
+                .get("quantity")
+                .and_then(|quantity| quantity.to_string().parse::<f32>().ok())
+                .filter(|quantity| *quantity > 0.0);
+            let unit = texts(ingredient.get("unit")).into_iter().next();
+
+            quantity.map_or_else(
+                || food.clone(),
+                |quantity| {
+                    ingredient_formatter::format_parts(
+                        &food,
+                        quantity,
+                        unit.as_deref().unwrap_or_default(),
+                    )
+                },
+            )
+        });
+
+    field(ingredient, "originalText")
+        .or(structured)
+        .or_else(|| field(ingredient, "display"))
+        .or_else(|| field(ingredient, "note"))
+}
+
+fn map_recipe(recipe: &Value) -> Result<RecipeIn, ImportError> {
+    let Some(name) = field(recipe, "name") else {
+        return Err(ImportError::MissingField("name"));
+    };
+
+    let ingredients = recipe
+        .get("recipeIngredient")
+        .and_then(Value::as_array)
+        .map(|ingredients| ingredients.iter().filter_map(ingredient_line).collect())
+        .unwrap_or_default();
+
+    let steps = recipe
+        .get("recipeInstructions")
+        .and_then(Value::as_array)
+        .map(|instructions| {
+            instructions
+                .iter()
+                .filter_map(|instruction| {
+                    instruction
+                        .as_str()
+                        .map(clean_text)
+                        .or_else(|| field(instruction, "text"))
+                })
+                .filter(|step| !step.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let notes = recipe.get("notes").and_then(Value::as_array).map(|notes| {
+        notes
+            .iter()
+            .map(|note| {
+                [field(note, "title"), field(note, "text")]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    });
+
+    let time = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| field(recipe, key).as_deref().and_then(parse_minutes))
+    };
+
+    Ok(RecipeIn {
+        name,
+        ingredients,
+        steps,
+        description: field(recipe, "description"),
+        prep_time_minutes: time(&["prepTime"]),
+        cook_time_minutes: time(&["cookTime", "performTime"]),
+        difficulty: None,
+        source_url: field(recipe, "orgURL").filter(|url| url.starts_with("http")),
+        notes: notes.filter(|notes| !notes.trim().is_empty()),
+        tags: collect_tags(
+            texts(recipe.get("tags"))
+                .into_iter()
+                .chain(texts(recipe.get("recipeCategory"))),
+        ),
+    })
+}
+
+pub fn parse(document: &str) -> Result<Vec<RecipeIn>, ImportError> {
+    let value: Value = match serde_json::from_str(document) {
+        Ok(value) => value,
+        Err(error) => return Err(ImportError::InvalidDocument(error.to_string())),
+    };
+
+    let recipes: Vec<&Value> = match &value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(object) => object
+            .get("items")
+            .and_then(Value::as_array)
+            .map_or_else(|| vec![&value], |items| items.iter().collect()),
+        _ => Vec::new(),
+    };
+
+    if recipes.is_empty() {
+        return Err(ImportError::NoRecipeFound());
+    }
+
+    recipes.into_iter().map(map_recipe).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mealie_test() {
+        let document = r#"{
+            "name": "Gratin de gnocchi",
+            "slug": "gratin-de-gnocchi",
+            "description": "Un gratin tout simple.",
+            "prepTime": "15 minutes",
+            "performTime": "PT30M",
+            "orgURL": "https://www.example.com/gratin",
+            "recipeCategory": [{"name": "Plat"}],
+            "tags": [{"name": "Gratin", "slug": "gratin"}],
+            "recipeIngredient": [
+                {"quantity": 400.0, "unit": {"name": "g"}, "food": {"name": "gnocchi"}, "note": "", "originalText": null},
+                {"quantity": 0, "unit": null, "food": null, "note": "30g de beurre", "display": ""},
+                {"quantity": 0, "unit": null, "food": {"name": "sel"}, "note": ""},
+                "0.5 litre de lait"
+            ],
+            "recipeInstructions": [
+                {"title": "", "text": "Faire cuire les gnocchi."},
+                {"title": "", "text": "Enfourner pour 20 minutes."}
+            ],
+            "notes": [{"title": "Astuce", "text": "Servir aussit\u00f4t."}]
+        }"#;
+
+        let recipes = parse(document).unwrap();
+
+        assert_eq!(
+            recipes,
+            vec![RecipeIn {
+                name: "Gratin de gnocchi".to_owned(),
+                ingredients: vec![
+                    "400 g de gnocchi".to_owned(),
+                    "30g de beurre".to_owned(),
+                    "sel".to_owned(),
+                    "0.5 litre de lait".to_owned()
+                ],
+                steps: vec![
+                    "Faire cuire les gnocchi.".to_owned(),
+                    "Enfourner pour 20 minutes.".to_owned()
+                ],
+                description: Some("Un gratin tout simple.".to_owned()),
+                prep_time_minutes: Some(15),
+                cook_time_minutes: Some(30),
+                difficulty: None,
+                source_url: Some("https://www.example.com/gratin".to_owned()),
+                notes: Some("Astuce\nServir aussit\u{f4}t.".to_owned()),
+                tags: vec!["gratin".to_owned(), "plat".to_owned()],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_items_page_test() {
+        let document = r#"{"page": 1, "items": [
+            {"name": "Tapenade", "recipeIngredient": ["8 c\u00e2pres"], "recipeInstructions": ["Mixer."]},
+            {"name": "Houmous", "recipeIngredient": ["400 g de pois chiches"], "recipeInstructions": ["Mixer."]}
+        ]}"#;
+
+        let names: Vec<String> = parse(document)
+            .unwrap()
+            .into_iter()
+            .map(|recipe| recipe.name)
+            .collect();
+
+        assert_eq!(names, vec!["Tapenade", "Houmous"]);
+    }
+}
@@ -0,0 +1,292 @@
+pub mod cooklang;
+pub mod jsonld;
+pub mod markdown;
+pub mod mealie;
+pub mod paprika;
+
+use crate::ingredient_parser;
+use crate::models::{ImportFormat, RecipeIn};
+use crate::text::fold;
+use crate::validation::DIFFICULTIES;
+
+use regex::Regex;
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ImportError {
+    InvalidDocument(String),
+    NoRecipeFound(),
+    MissingField(&'static str),
+    UnexpectedRecipeCount(usize),
+}
+
+impl ImportError {
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidDocument(_) => "invalid_document",
+            Self::NoRecipeFound() => "no_recipe_found",
+            Self::MissingField(_) => "missing_field",
+            Self::UnexpectedRecipeCount(_) => "unexpected_recipe_count",
+        }
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDocument(reason) => write!(f, "Invalid document: {reason}"),
+            Self::NoRecipeFound() => write!(f, "No recipe found in document"),
+            Self::MissingField(field) => write!(f, "Recipe has no {field}"),
+            Self::UnexpectedRecipeCount(count) => {
+                write!(f, "Document contains {count} recipes, expected one")
+            }
+        }
+    }
+}
+
+fn decode_html_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| (&rest[1..end], end));
+
+        let replacement = entity.and_then(|(name, end)| {
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "agrave" => Some('\u{e0}'),
+                "acirc" => Some('\u{e2}'),
+                "ccedil" => Some('\u{e7}'),
+                "egrave" => Some('\u{e8}'),
+                "eacute" => Some('\u{e9}'),
+                "ecirc" => Some('\u{ea}'),
+                "icirc" => Some('\u{ee}'),
+                "iuml" => Some('\u{ef}'),
+                "ocirc" => Some('\u{f4}'),
+                "ugrave" => Some('\u{f9}'),
+                "ucirc" => Some('\u{fb}'),
+                "oelig" => Some('\u{153}'),
+                "rsquo" => Some('\u{2019}'),
+                "deg" => Some('\u{b0}'),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map_or_else(
+                        || name.strip_prefix('#').and_then(|n| n.parse::<u32>().ok()),
+                        |n| u32::from_str_radix(n, 16).ok(),
+                    )
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        if let Some((c, end)) = replacement {
+            decoded.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            decoded.push('&');
+            rest = &rest[1..];
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[must_use]
+pub fn clean_text(text: &str) -> String {
+    decode_html_entities(text)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn split_ingredients(lines: Vec<String>) -> (Vec<String>, Option<String>) {
+    let (ingredients, unparsed_ingredients): (Vec<String>, Vec<String>) = lines
+        .into_iter()
+        .partition(|line| ingredient_parser::parse(line).is_ok());
+
+    let notes = if unparsed_ingredients.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Ingr\u{e9}dients sans quantit\u{e9} : {}",
+            unparsed_ingredients.join(", ")
+        ))
+    };
+
+    (ingredients, notes)
+}
+
+fn collect_tags(raw_tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for tag in raw_tags {
+        let tag = tag.trim().to_lowercase();
+
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+fn join_notes(notes: impl IntoIterator<Item = Option<String>>) -> Option<String> {
+    let notes: Vec<String> = notes
+        .into_iter()
+        .flatten()
+        .filter(|note| !note.trim().is_empty())
+        .collect();
+
+    if notes.is_empty() {
+        None
+    } else {
+        Some(notes.join("\n\n"))
+    }
+}
+
+fn strip_step_number(line: &str) -> Option<&str> {
+    let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+
+    if rest.len() == line.len() {
+        return None;
+    }
+
+    rest.strip_prefix(['.', ')']).map(str::trim_start)
+}
+
+fn parse_minutes(text: &str) -> Option<i32> {
+    let text = text.trim();
+
+    if text.starts_with('P') {
+        return jsonld::parse_duration(text);
+    }
+
+    let re = Regex::new(r"(\d+)(?:[.,](\d))?\d*\s*([[:alpha:]]*)").ok()?;
+
+    let mut tenths = None;
+
+    for caps in re.captures_iter(text) {
+        let whole = caps.get(1)?.as_str().parse::<i64>().ok()?;
+        let fraction = caps
+            .get(2)
+            .and_then(|fraction| fraction.as_str().parse::<i64>().ok())
+            .unwrap_or(0);
+
+        let unit = fold(caps.get(3).map_or("", |unit| unit.as_str()));
+        let multiplier = if unit.starts_with('h') {
+            60
+        } else if unit.starts_with('d') || unit.starts_with('j') {
+            24 * 60
+        } else {
+            1
+        };
+
+        let component = whole
+            .checked_mul(10)?
+            .checked_add(fraction)?
+            .checked_mul(multiplier)?;
+
+        tenths = Some(tenths.unwrap_or(0_i64).checked_add(component)?);
+    }
+
+    i32::try_from(tenths?.checked_add(5)? / 10).ok()
+}
+
+fn parse_difficulty(text: &str) -> Option<String> {
+    let folded = fold(text.trim());
+
+    let difficulty = match folded.as_str() {
+        "very easy" | "tres facile" => DIFFICULTIES[0],
+        "easy" | "facile" => DIFFICULTIES[1],
+        "medium" | "intermediate" | "moyen" | "moyenne" => DIFFICULTIES[2],
+        "hard" | "difficult" | "difficile" => DIFFICULTIES[3],
+        _ => return None,
+    };
+
+    Some(difficulty.to_owned())
+}
+
+fn texts(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(text)) => vec![clean_text(text)],
+        Some(Value::Array(items)) => items.iter().flat_map(|item| texts(Some(item))).collect(),
+        Some(Value::Object(object)) => texts(object.get("name").or_else(|| object.get("@id"))),
+        _ => Vec::new(),
+    }
+    .into_iter()
+    .filter(|text| !text.is_empty())
+    .collect()
+}
+
+pub fn parse(
+    format: ImportFormat,
+    document: &str,
+    default_name: Option<&str>,
+) -> Result<Vec<RecipeIn>, ImportError> {
+    match format {
+        ImportFormat::Jsonld => jsonld::parse(document).map(|recipe_in| vec![recipe_in]),
+        ImportFormat::Markdown => markdown::parse(document).map(|recipe_in| vec![recipe_in]),
+        ImportFormat::Cooklang => {
+            cooklang::parse(document, default_name).map(|recipe_in| vec![recipe_in])
+        }
+        ImportFormat::Paprika => paprika::parse(document),
+        ImportFormat::Mealie => mealie::parse(document),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Saumon  fum&eacute;", "Saumon fum\u{e9}")]
+    #[case("Sel &amp; poivre", "Sel & poivre")]
+    #[case("l&#39;huile d&#x27;olive", "l'huile d'olive")]
+    #[case("  Cr\u{e8}me&nbsp;fra\u{ee}che\n", "Cr\u{e8}me fra\u{ee}che")]
+    #[case("A & B", "A & B")]
+    fn clean_text_test(#[case] test_input: &str, #[case] expected: &str) {
+        assert_eq!(clean_text(test_input), expected);
+    }
+
+    #[rstest]
+    #[case("PT1H30M", Some(90))]
+    #[case("15 mins", Some(15))]
+    #[case("1 hr 10 mins", Some(70))]
+    #[case("1h30", Some(90))]
+    #[case("1,5 heure", Some(90))]
+    #[case("2 jours", Some(2880))]
+    #[case("rapide", None)]
+    #[case("99999999 jours", None)]
+    #[case("2000000000 minutes", Some(2_000_000_000))]
+    #[case("3000000000 minutes", None)]
+    #[case("P9999999D", None)]
+    #[case("922337203685477580.7 minutes", None)]
+    fn parse_minutes_test(#[case] test_input: &str, #[case] expected: Option<i32>) {
+        assert_eq!(parse_minutes(test_input), expected);
+    }
+
+    #[rstest]
+    #[case("Easy", Some("facile"))]
+    #[case("Tr\u{e8}s facile", Some("tr\u{e8}s facile"))]
+    #[case("Hard", Some("difficile"))]
+    #[case("5 stars", None)]
+    fn parse_difficulty_test(#[case] test_input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_difficulty(test_input).as_deref(), expected);
+    }
+}
@@ -0,0 +1,128 @@
+use super::{
+    clean_text, collect_tags, parse_difficulty, parse_minutes, strip_step_number, texts,
+    ImportError,
+};
+use crate::models::RecipeIn;
+
+use serde_json::Value;
+
+fn lines(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_str)
+        .map(|text| {
+            text.lines()
+                .map(clean_text)
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn map_recipe(recipe: &Value) -> Result<RecipeIn, ImportError> {
+    let text = |key: &str| texts(recipe.get(key)).into_iter().next();
+
+    let Some(name) = text("name") else {
+        return Err(ImportError::MissingField("name"));
+    };
+
+    let ingredients = lines(recipe.get("ingredients"));
+
+    let steps = lines(recipe.get("directions"))
+        .iter()
+        .map(|line| strip_step_number(line).unwrap_or(line).to_owned())
+        .collect();
+
+    let notes = recipe
+        .get("notes")
+        .and_then(Value::as_str)
+        .map(|notes| notes.trim().to_owned())
+        .filter(|notes| !notes.is_empty());
+
+    Ok(RecipeIn {
+        name,
+        ingredients,
+        steps,
+        description: text("description"),
+        prep_time_minutes: text("prep_time").as_deref().and_then(parse_minutes),
+        cook_time_minutes: text("cook_time").as_deref().and_then(parse_minutes),
+        difficulty: text("difficulty").as_deref().and_then(parse_difficulty),
+        source_url: text("source_url").filter(|url| url.starts_with("http")),
+        notes,
+        tags: collect_tags(texts(recipe.get("categories"))),
+    })
+}
+
+pub fn parse(document: &str) -> Result<Vec<RecipeIn>, ImportError> {
+    let value: Value = match serde_json::from_str(document) {
+        Ok(value) => value,
+        Err(error) => return Err(ImportError::InvalidDocument(error.to_string())),
+    };
+
+    let recipes: Vec<&Value> = match &value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![&value],
+        _ => Vec::new(),
+    };
+
+    if recipes.is_empty() {
+        return Err(ImportError::NoRecipeFound());
+    }
+
+    recipes.into_iter().map(map_recipe).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_paprika_test() {
+        let document = r#"{
+            "uid": "0A1B2C",
+            "name": "Tapenade",
+            "ingredients": "200 g d'olives noires\n8 c\u00e2pres\n\nhuile d'olive",
+            "directions": "1. Hacher l'ail.\n2. Mixer le tout.",
+            "description": "",
+            "notes": "Se conserve une semaine.",
+            "prep_time": "10 mins",
+            "cook_time": "",
+            "difficulty": "Easy",
+            "source_url": "https://www.example.com/tapenade",
+            "categories": ["Ap\u00e9ritif", "ap\u00e9ritif", "Proven\u00e7al"]
+        }"#;
+
+        let recipes = parse(document).unwrap();
+
+        assert_eq!(recipes.len(), 1);
+        assert_eq!(
+            recipes[0],
+            RecipeIn {
+                name: "Tapenade".to_owned(),
+                ingredients: vec![
+                    "200 g d'olives noires".to_owned(),
+                    "8 c\u{e2}pres".to_owned(),
+                    "huile d'olive".to_owned()
+                ],
+                steps: vec!["Hacher l'ail.".to_owned(), "Mixer le tout.".to_owned()],
+                description: None,
+                prep_time_minutes: Some(10),
+                cook_time_minutes: None,
+                difficulty: Some("facile".to_owned()),
+                source_url: Some("https://www.example.com/tapenade".to_owned()),
+                notes: Some("Se conserve une semaine.".to_owned()),
+                tags: vec!["ap\u{e9}ritif".to_owned(), "proven\u{e7}al".to_owned()],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("[]", ImportError::NoRecipeFound())]
+    #[case(
+        r#"[{"name": "Tapenade"}, {"ingredients": "8 c\u00e2pres"}]"#,
+        ImportError::MissingField("name")
+    )]
+    fn parse_error_test(#[case] document: &str, #[case] expected: ImportError) {
+        assert_eq!(parse(document).unwrap_err(), expected);
+    }
+}
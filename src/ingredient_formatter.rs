@@ -0,0 +1,115 @@
+use crate::models::IngredientOut;
+use crate::text::fold;
+
+const ABBREVIATED_UNITS: [&str; 12] = [
+    "g", "kg", "mg", "l", "cl", "dl", "ml", "cs", "cc", "oz", "lb", "tbsp",
+];
+
+const PREPOSITIONS: [&str; 9] = ["de", "d", "du", "des", "a", "au", "aux", "en", "et"];
+
+fn starts_with_vowel(name: &str) -> bool {
+    const VOWELS: [char; 7] = ['a', 'e', 'i', 'o', 'u', 'y', 'h'];
+
+    fold(name).starts_with(VOWELS)
+}
+
+fn format_quantity(quantity: f32) -> String {
+    let formatted = format!("{quantity:.3}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    trimmed.replace('.', ",")
+}
+
+fn pluralize_word(word: &str) -> String {
+    if word.ends_with(['s', 'x', 'z']) {
+        word.to_owned()
+    } else if word.ends_with("au") || word.ends_with("eu") {
+        format!("{word}x")
+    } else if let Some(stem) = word.strip_suffix("al") {
+        format!("{stem}aux")
+    } else {
+        format!("{word}s")
+    }
+}
+
+fn pluralize(text: &str) -> String {
+    let mut reached_complement = false;
+
+    text.split(' ')
+        .map(|word| {
+            let head = fold(word);
+            let head = head.split(['\'', '\u{2019}']).next().unwrap_or_default();
+
+            reached_complement |= PREPOSITIONS.contains(&head);
+
+            if reached_complement || word.is_empty() {
+                word.to_owned()
+            } else {
+                pluralize_word(word)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn is_plural(quantity: f32) -> bool {
+    quantity >= 2.0
+}
+
+#[must_use]
+pub fn format_parts(name: &str, quantity: f32, unit: &str) -> String {
+    let quantity_str = format_quantity(quantity);
+
+    if unit.is_empty() {
+        let name = if is_plural(quantity) {
+            pluralize(name)
+        } else {
+            name.to_owned()
+        };
+
+        return format!("{quantity_str} {name}");
+    }
+
+    let unit = if is_plural(quantity) && !ABBREVIATED_UNITS.contains(&fold(unit).as_str()) {
+        pluralize(unit)
+    } else {
+        unit.to_owned()
+    };
+
+    let preposition = if starts_with_vowel(name) { "d'" } else { "de " };
+
+    format!("{quantity_str} {unit} {preposition}{name}")
+}
+
+#[must_use]
+pub fn format(ingredient: &IngredientOut) -> String {
+    format_parts(&ingredient.name, ingredient.quantity, &ingredient.unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(("saumon fum\u{e9}", 125.0, "g"), "125 g de saumon fum\u{e9}")]
+    #[case(("oignon", 1.0, ""), "1 oignon")]
+    #[case(("oignon rouge", 3.0, ""), "3 oignons rouges")]
+    #[case(("ail", 1.0, "gousse"), "1 gousse d'ail")]
+    #[case(("ail", 0.5, "gousse"), "0,5 gousse d'ail")]
+    #[case(("ail", 2.0, "gousse"), "2 gousses d'ail")]
+    #[case(("\u{e9}pinards surgel\u{e9}s", 300.0, "g"), "300 g d'\u{e9}pinards surgel\u{e9}s")]
+    #[case(("lait", 0.5, "litre"), "0,5 litre de lait")]
+    #[case(("lait", 1.5, "L"), "1,5 L de lait")]
+    #[case(("saumon fum\u{e9}", 120.989, "g"), "120,989 g de saumon fum\u{e9}")]
+    #[case(("beurre", 2.0, "morceau"), "2 morceaux de beurre")]
+    #[case(("huile d'olive", 2.0, "cuill\u{e8}re \u{e0} soupe"), "2 cuill\u{e8}res \u{e0} soupe d'huile d'olive")]
+    #[case(("saucisse de Montb\u{e9}liard", 4.0, ""), "4 saucisses de Montb\u{e9}liard")]
+    #[case(("c\u{e2}pres", 8.0, ""), "8 c\u{e2}pres")]
+    fn ingredient_formatting_test(#[case] ingredient: (&str, f32, &str), #[case] expected: &str) {
+        assert_eq!(
+            format_parts(ingredient.0, ingredient.1, ingredient.2),
+            expected
+        );
+    }
+}
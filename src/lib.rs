@@ -1,25 +1,71 @@
+pub mod archive;
+pub mod config;
 pub mod db;
 pub mod db_utils;
+pub mod exporters;
+pub mod importers;
+pub mod ingredient_formatter;
 pub mod ingredient_parser;
 pub mod models;
+pub mod openapi;
 pub mod response;
 pub mod router;
 pub mod schema;
 pub mod script;
+pub mod seed;
+pub mod shopping_list;
+pub mod step_parser;
+pub mod text;
+pub mod validation;
 
+use crate::router::archive as archive_router;
 use crate::router::carts as cart_router;
+use crate::router::catchers;
+use crate::router::docs as docs_router;
+use crate::router::health as health_router;
 use crate::router::recipes as recipe_router;
-
-pub const DATABASE_URL: &str = "cooking_book.db";
+use crate::router::tags as tag_router;
 
 #[must_use]
 pub fn create_app() -> rocket::Rocket<rocket::Build> {
     rocket::build()
+        .attach(db::stage())
+        .attach(db::migrations())
+        .attach(openapi::stage())
+        .register(
+            "/",
+            rocket::catchers![
+                catchers::bad_request,
+                catchers::not_found,
+                catchers::unprocessable_entity,
+                catchers::internal_server_error,
+                catchers::service_unavailable,
+                catchers::default
+            ],
+        )
+        .mount(
+            "/",
+            rocket::routes![health_router::health, health_router::ready],
+        )
+        .mount(
+            "/api",
+            rocket::routes![
+                archive_router::export,
+                archive_router::import,
+                docs_router::specification,
+                docs_router::docs,
+                docs_router::script,
+                docs_router::stylesheet,
+            ],
+        )
         .mount(
             "/api/carts",
             rocket::routes![
                 cart_router::create,
                 cart_router::retrieve,
+                cart_router::export_markdown,
+                cart_router::retrieve_shopping_list,
+                cart_router::export_shopping_list_pdf,
                 cart_router::delete,
                 cart_router::add_recipe,
             ],
@@ -28,8 +74,24 @@ pub fn create_app() -> rocket::Rocket<rocket::Build> {
             "/api/recipes",
             rocket::routes![
                 recipe_router::create,
+                recipe_router::create_from_markdown,
+                recipe_router::import_collection,
+                recipe_router::import,
                 recipe_router::retrieve_all,
                 recipe_router::retrieve,
+                recipe_router::retrieve_text,
+                recipe_router::retrieve_file,
+                recipe_router::create_step,
+                recipe_router::move_step,
+                recipe_router::delete_step,
+            ],
+        )
+        .mount(
+            "/api/tags",
+            rocket::routes![
+                tag_router::retrieve_all,
+                tag_router::rename,
+                tag_router::merge,
             ],
         )
 }
@@ -1,10 +1,45 @@
+use clap::Parser;
+use cooking_book::config::DATABASE_KEY;
 use cooking_book::create_app;
-use cooking_book::db;
-use cooking_book::DATABASE_URL;
+
+#[derive(Parser)]
+#[command(about = "Run the cooking book HTTP server")]
+struct Args {
+    /// Apply pending database migrations at launch, overriding the `database.migrate` setting
+    #[arg(long, overrides_with = "no_migrate")]
+    migrate: bool,
+
+    /// Leave pending database migrations alone, overriding the `database.migrate` setting
+    #[arg(long, overrides_with = "migrate")]
+    no_migrate: bool,
+}
+
+impl Args {
+    const fn migrate(&self) -> Option<bool> {
+        match (self.migrate, self.no_migrate) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(not(tarpaulin_include))]
 #[must_use]
 #[rocket::launch]
 pub fn rocket() -> rocket::Rocket<rocket::Build> {
-    create_app().manage(db::connect(DATABASE_URL))
+    let args = Args::parse();
+    let app = create_app();
+
+    match args.migrate() {
+        Some(migrate) => {
+            let figment = app
+                .figment()
+                .clone()
+                .merge((format!("{DATABASE_KEY}.migrate"), migrate));
+
+            app.configure(figment)
+        }
+        None => app,
+    }
 }
@@ -1,14 +1,27 @@
-use crate::schema::{cart_recipes, carts, ingredients, recipes, steps};
+use crate::ingredient_formatter;
+use crate::schema::{
+    cart_recipes, carts, ingredients, recipe_tags, recipes, step_ingredients, steps, tags,
+};
 use diesel::prelude::{Associations, Identifiable, Queryable, Selectable};
+use rocket::FromFormField;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct RecipeIn {
     pub name: String,
     pub ingredients: Vec<String>,
     pub steps: Vec<String>,
+    pub description: Option<String>,
+    pub prep_time_minutes: Option<i32>,
+    pub cook_time_minutes: Option<i32>,
+    pub difficulty: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Queryable, Identifiable, Selectable)]
@@ -17,6 +30,12 @@ pub struct RecipeIn {
 pub struct Recipe {
     pub id: i32,
     pub name: String,
+    pub description: Option<String>,
+    pub prep_time_minutes: Option<i32>,
+    pub cook_time_minutes: Option<i32>,
+    pub difficulty: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
 }
 
 #[derive(Queryable, Selectable, Identifiable, Associations)]
@@ -32,15 +51,31 @@ pub struct Ingredient {
     pub unit: String,
 }
 
-#[derive(Debug, PartialEq, Queryable, Identifiable, Selectable, Serialize, Deserialize)]
-#[diesel(table_name = ingredients)]
-#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct IngredientOut {
     pub id: i32,
     pub preposition: String,
     pub name: String,
     pub quantity: f32,
     pub unit: String,
+    pub display: String,
+}
+
+impl From<Ingredient> for IngredientOut {
+    fn from(ingredient: Ingredient) -> Self {
+        Self {
+            display: ingredient_formatter::format_parts(
+                &ingredient.name,
+                ingredient.quantity,
+                &ingredient.unit,
+            ),
+            id: ingredient.id,
+            preposition: ingredient.preposition,
+            name: ingredient.name,
+            quantity: ingredient.quantity,
+            unit: ingredient.unit,
+        }
+    }
 }
 
 #[derive(Queryable, Identifiable, Selectable, Associations, Serialize)]
@@ -51,14 +86,82 @@ pub struct Step {
     pub id: i32,
     pub recipe_id: i32,
     pub description: String,
+    pub position: i32,
+    pub duration_minutes: Option<i32>,
+    pub temperature_celsius: Option<i32>,
+    pub passive: bool,
+}
+
+#[derive(Queryable, Identifiable, Selectable, Associations)]
+#[diesel(primary_key(step_id, ingredient_id))]
+#[diesel(belongs_to(Step))]
+#[diesel(table_name = step_ingredients)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct StepIngredient {
+    pub step_id: i32,
+    pub ingredient_id: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct StepOut {
+    pub id: i32,
+    pub position: i32,
+    pub description: String,
+    pub duration_minutes: Option<i32>,
+    pub temperature_celsius: Option<i32>,
+    pub passive: bool,
+    pub ingredient_ids: Vec<i32>,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
+impl From<(Step, Vec<i32>)> for StepOut {
+    fn from((step, ingredient_ids): (Step, Vec<i32>)) -> Self {
+        Self {
+            id: step.id,
+            position: step.position,
+            description: step.description,
+            duration_minutes: step.duration_minutes,
+            temperature_celsius: step.temperature_celsius,
+            passive: step.passive,
+            ingredient_ids,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct StepIn {
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct StepPositionIn {
+    pub position: i32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct RecipeWithIngredientsOut {
     pub id: i32,
     pub name: String,
+    pub description: Option<String>,
+    pub prep_time_minutes: Option<i32>,
+    pub cook_time_minutes: Option<i32>,
+    pub difficulty: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
     pub ingredients: Vec<IngredientOut>,
-    pub steps: Vec<String>,
+    pub steps: Vec<StepOut>,
+    pub active_time_minutes: i32,
+    pub passive_time_minutes: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum RecipeSort {
+    #[field(value = "name")]
+    Name,
+    #[field(value = "total_time")]
+    TotalTime,
 }
 
 #[derive(Queryable, Identifiable, Selectable)]
@@ -80,14 +183,197 @@ pub struct CartRecipe {
     pub recipe_id: i32,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Queryable, Identifiable, Selectable)]
+#[diesel(table_name = tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Queryable, Identifiable, Selectable, Associations)]
+#[diesel(primary_key(recipe_id, tag_id))]
+#[diesel(belongs_to(Recipe))]
+#[diesel(belongs_to(Tag))]
+#[diesel(table_name = recipe_tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct RecipeTag {
+    pub recipe_id: i32,
+    pub tag_id: i32,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TagIn {
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Queryable, Deserialize, Serialize, JsonSchema)]
+pub struct TagOut {
+    pub id: i32,
+    pub name: String,
+    pub recipe_count: i64,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
 pub struct CartWithRecipesOut {
     pub id: i32,
     pub created_at: String,
     pub recipes: Vec<RecipeWithIngredientsOut>,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ShoppingListItemOut {
+    pub name: String,
+    pub quantity: f32,
+    pub unit: String,
+    pub display: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct AisleOut {
+    pub name: String,
+    pub items: Vec<ShoppingListItemOut>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ShoppingListOut {
+    pub cart_id: i32,
+    pub aisles: Vec<AisleOut>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ArchivedIngredient {
+    pub preposition: String,
+    pub name: String,
+    pub quantity: f32,
+    pub unit: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ArchivedStep {
+    pub description: String,
+    pub duration_minutes: Option<i32>,
+    pub temperature_celsius: Option<i32>,
+    pub passive: bool,
+    #[serde(default)]
+    pub ingredients: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ArchivedRecipe {
+    pub name: String,
+    pub description: Option<String>,
+    pub prep_time_minutes: Option<i32>,
+    pub cook_time_minutes: Option<i32>,
+    pub difficulty: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub ingredients: Vec<ArchivedIngredient>,
+    #[serde(default)]
+    pub steps: Vec<ArchivedStep>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ArchivedCart {
+    pub created_at: String,
+    #[serde(default)]
+    pub recipes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct Archive {
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub recipes: Vec<ArchivedRecipe>,
+    #[serde(default)]
+    pub carts: Vec<ArchivedCart>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, FromFormField, clap::ValueEnum)]
+pub enum ImportFormat {
+    #[default]
+    #[field(value = "jsonld")]
+    Jsonld,
+    #[field(value = "markdown")]
+    Markdown,
+    #[field(value = "cooklang")]
+    Cooklang,
+    #[field(value = "paprika")]
+    Paprika,
+    #[field(value = "mealie")]
+    Mealie,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum CollectionFormat {
+    #[field(value = "paprika")]
+    Paprika,
+    #[field(value = "mealie")]
+    Mealie,
+}
+
+impl From<CollectionFormat> for ImportFormat {
+    fn from(format: CollectionFormat) -> Self {
+        match format {
+            CollectionFormat::Paprika => Self::Paprika,
+            CollectionFormat::Mealie => Self::Mealie,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, FromFormField, clap::ValueEnum)]
+pub enum MergeStrategy {
+    #[default]
+    #[field(value = "skip")]
+    Skip,
+    #[field(value = "overwrite")]
+    Overwrite,
+    #[field(value = "rename")]
+    Rename,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct RenamedRecipeOut {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ImportReportOut {
+    pub created: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+    pub renamed: Vec<RenamedRecipeOut>,
+    pub carts_created: usize,
+    pub carts_skipped: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct HealthOut {
+    pub status: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct PoolStatsOut {
+    pub max_size: u32,
+    pub connections: u32,
+    pub idle_connections: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ReadinessOut {
+    pub status: String,
+    pub schema_version: Option<String>,
+    pub pool: PoolStatsOut,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
 pub struct Data<T> {
     pub data: T,
 }
@@ -0,0 +1,259 @@
+use crate::models::Data;
+use crate::response::Errors;
+use crate::router::{archive, carts, docs, health, recipes, tags};
+
+use rocket::fairing::AdHoc;
+use rocket::serde::json::{json, Value};
+use rocket::Route;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::Map;
+
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+pub struct Specification(pub Value);
+
+pub enum Content {
+    Json(Value),
+    Documents(&'static [&'static str]),
+    Empty,
+}
+
+pub struct Operation {
+    summary: &'static str,
+    path: Option<&'static str>,
+    request: Option<Content>,
+    status: u16,
+    response: Content,
+}
+
+impl Operation {
+    #[must_use]
+    pub const fn new(summary: &'static str, status: u16, response: Content) -> Self {
+        Self {
+            summary,
+            path: None,
+            request: None,
+            status,
+            response,
+        }
+    }
+
+    #[must_use]
+    pub fn with_request(self, request: Content) -> Self {
+        Self {
+            request: Some(request),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn at(self, path: &'static str) -> Self {
+        Self {
+            path: Some(path),
+            ..self
+        }
+    }
+}
+
+pub fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Value {
+    serde_json::to_value(generator.subschema_for::<T>()).unwrap_or_default()
+}
+
+pub fn data<T: JsonSchema>(generator: &mut SchemaGenerator) -> Content {
+    Content::Json(schema::<Data<T>>(generator))
+}
+
+fn describe(route: &Route, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let name = route.name.as_deref().unwrap_or_default();
+
+    match route.uri.base() {
+        "/" => health::describe(name, generator),
+        "/api" => archive::describe(name, generator).or_else(|| docs::describe(name, generator)),
+        "/api/carts" => carts::describe(name, generator),
+        "/api/recipes" => recipes::describe(name, generator),
+        "/api/tags" => tags::describe(name, generator),
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn undocumented<'a>(routes: impl Iterator<Item = &'a Route>) -> Vec<String> {
+    let mut generator = SchemaGenerator::new(SchemaSettings::openapi3());
+
+    routes
+        .filter(|route| {
+            describe(route, &mut generator).map_or(true, |operation| operation.summary.is_empty())
+        })
+        .map(|route| format!("{} {}", route.method, route.uri))
+        .collect()
+}
+
+fn content(body: Content) -> Option<Value> {
+    match body {
+        Content::Json(schema) => Some(json!({ "application/json": { "schema": schema } })),
+        Content::Documents(media_types) => Some(Value::Object(
+            media_types
+                .iter()
+                .map(|media_type| {
+                    (
+                        (*media_type).to_owned(),
+                        json!({ "schema": { "type": "string" } }),
+                    )
+                })
+                .collect(),
+        )),
+        Content::Empty => None,
+    }
+}
+
+fn path_template(route: &Route) -> String {
+    route
+        .uri
+        .path()
+        .split('/')
+        .map(|segment| {
+            segment.strip_prefix('<').map_or_else(
+                || segment.to_owned(),
+                |name| format!("{{{}}}", name.trim_end_matches('>')),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parameter(name: &str, location: &str) -> Value {
+    let schema = if name.ends_with("_id") || name == "position" {
+        json!({ "type": "integer", "format": "int32" })
+    } else {
+        json!({ "type": "string" })
+    };
+
+    json!({
+        "name": name,
+        "in": location,
+        "required": location == "path",
+        "schema": schema,
+    })
+}
+
+fn parameters(path: &str, route: &Route) -> Vec<Value> {
+    let path_parameters = path
+        .split('{')
+        .skip(1)
+        .filter_map(|segment| segment.split_once('}'))
+        .map(|(name, _)| parameter(name, "path"));
+
+    let query_parameters = route
+        .uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|segment| segment.strip_prefix('<'))
+        .map(|name| parameter(name.trim_end_matches('>'), "query"));
+
+    path_parameters.chain(query_parameters).collect()
+}
+
+fn operation(route: &Route, generator: &mut SchemaGenerator) -> (String, Value) {
+    let description = describe(route, generator);
+    let path = description
+        .as_ref()
+        .and_then(|operation| operation.path)
+        .map_or_else(|| path_template(route), str::to_owned);
+
+    let mut responses = Map::new();
+    responses.insert(
+        "default".to_owned(),
+        json!({
+            "description": "Error",
+            "content": { "application/json": { "schema": schema::<Errors>(generator) } },
+        }),
+    );
+
+    let mut operation = Map::new();
+    operation.insert(
+        "operationId".to_owned(),
+        Value::String(format!(
+            "{}_{}",
+            route.uri.base().trim_matches('/').replace('/', "_"),
+            route.name.as_deref().unwrap_or_default()
+        )),
+    );
+
+    if let Some(description) = description {
+        operation.insert(
+            "summary".to_owned(),
+            Value::String(description.summary.to_owned()),
+        );
+
+        if let Some(request) = description.request.and_then(content) {
+            operation.insert(
+                "requestBody".to_owned(),
+                json!({ "required": true, "content": request }),
+            );
+        }
+
+        let mut response = json!({ "description": description.summary });
+        if let Some(body) = content(description.response) {
+            response["content"] = body;
+        }
+        responses.insert(description.status.to_string(), response);
+    }
+
+    let parameters = parameters(&path, route);
+    if !parameters.is_empty() {
+        operation.insert("parameters".to_owned(), Value::Array(parameters));
+    }
+    operation.insert("responses".to_owned(), Value::Object(responses));
+
+    (path, Value::Object(operation))
+}
+
+fn merge(target: &mut Value, addition: Value) {
+    if let (Value::Object(target), Value::Object(addition)) = (target, addition) {
+        for (key, value) in addition {
+            match target.get_mut(&key) {
+                Some(existing) if key == "schema" && *existing != value => {
+                    *existing = json!({ "oneOf": [existing.take(), value] });
+                }
+                Some(existing) => merge(existing, value),
+                None => {
+                    target.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn document<'a>(routes: impl Iterator<Item = &'a Route>) -> Value {
+    let mut generator = SchemaGenerator::new(SchemaSettings::openapi3());
+    let mut paths = json!({});
+
+    for route in routes {
+        let (path, operation) = operation(route, &mut generator);
+        let method = route.method.as_str().to_lowercase();
+
+        merge(&mut paths, json!({ path: { method: operation } }));
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": { "schemas": generator.take_definitions() },
+    })
+}
+
+#[must_use]
+pub fn stage() -> AdHoc {
+    AdHoc::on_ignite("OpenAPI document", |rocket| async {
+        let specification = Specification(document(rocket.routes()));
+
+        rocket.manage(specification)
+    })
+}
@@ -1,21 +1,82 @@
-use rocket::http::Status;
+use rocket::http::{ContentType, Header, Status};
 use rocket::response::Responder;
-use rocket::serde::json::Json;
+use rocket::serde::json::{Json, Value};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use crate::models::Data;
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub const RETRY_AFTER_SECONDS: u32 = 5;
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct HTTPError {
+    #[schemars(with = "u16")]
     pub status_code: Status,
+    pub code: String,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Errors {
     pub errors: Vec<HTTPError>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+    pub source: Option<String>,
+    pub meta: Option<Value>,
+}
+
+impl ErrorDetail {
+    #[must_use]
+    pub const fn new(code: &'static str, message: String) -> Self {
+        Self {
+            code,
+            message,
+            source: None,
+            meta: None,
+        }
+    }
+
+    #[must_use]
+    pub fn at(self, source: String) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn within(self, path: &str, index: usize) -> Self {
+        Self {
+            message: format!("{path}[{index}].{}", self.message),
+            source: self.source.map(|source| format!("/{path}/{index}{source}")),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_meta(self, meta: Value) -> Self {
+        Self {
+            meta: Some(meta),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 #[derive(Responder)]
 pub enum ErrorResponder {
     // #[response(status = 400)]
@@ -24,38 +85,88 @@ pub enum ErrorResponder {
     NotFound(Json<Errors>),
     #[response(status = 409)]
     Conflict(Json<Errors>),
+    #[response(status = 422)]
+    UnprocessableEntity(Json<Errors>),
     #[response(status = 500)]
     InternalServerError(Json<Errors>),
+    #[response(status = 503)]
+    ServiceUnavailable(Json<Errors>, Header<'static>),
+}
+
+#[must_use]
+pub fn error_code(status: Status) -> String {
+    status.reason().map_or_else(
+        || status.code.to_string(),
+        |reason| reason.to_lowercase().replace([' ', '-'], "_"),
+    )
+}
+
+fn errors(status: Status, details: Vec<ErrorDetail>) -> Json<Errors> {
+    Json(Errors {
+        errors: details
+            .into_iter()
+            .map(|detail| HTTPError {
+                status_code: status,
+                code: detail.code.to_owned(),
+                message: detail.message,
+                source: detail.source,
+                meta: detail.meta,
+            })
+            .collect(),
+    })
 }
 
 #[must_use]
-pub fn not_found_error(error_message: String) -> ErrorResponder {
-    ErrorResponder::NotFound(Json(Errors {
-        errors: vec![HTTPError {
-            status_code: Status::NotFound,
-            message: error_message,
-        }],
-    }))
+pub fn status_error(status: Status, error_message: String) -> (Status, Json<Errors>) {
+    (
+        status,
+        Json(Errors {
+            errors: vec![HTTPError {
+                status_code: status,
+                code: error_code(status),
+                message: error_message,
+                source: None,
+                meta: None,
+            }],
+        }),
+    )
 }
 
 #[must_use]
-pub fn conflict(error_message: String) -> ErrorResponder {
-    ErrorResponder::Conflict(Json(Errors {
-        errors: vec![HTTPError {
-            status_code: Status::Conflict,
-            message: error_message,
-        }],
-    }))
+pub fn not_found_error(code: &'static str, error_message: String) -> ErrorResponder {
+    ErrorResponder::NotFound(errors(
+        Status::NotFound,
+        vec![ErrorDetail::new(code, error_message)],
+    ))
+}
+
+#[must_use]
+pub fn conflict(detail: ErrorDetail) -> ErrorResponder {
+    ErrorResponder::Conflict(errors(Status::Conflict, vec![detail]))
+}
+
+#[must_use]
+pub fn unprocessable_entity(details: Vec<ErrorDetail>) -> ErrorResponder {
+    ErrorResponder::UnprocessableEntity(errors(Status::UnprocessableEntity, details))
 }
 
 #[must_use]
 pub fn internal_server_error() -> ErrorResponder {
-    ErrorResponder::InternalServerError(Json(Errors {
-        errors: vec![HTTPError {
-            status_code: Status::InternalServerError,
-            message: "Database error".to_owned(),
-        }],
-    }))
+    ErrorResponder::InternalServerError(errors(
+        Status::InternalServerError,
+        vec![ErrorDetail::new(
+            "database_error",
+            "Database error".to_owned(),
+        )],
+    ))
+}
+
+#[must_use]
+pub fn service_unavailable(details: Vec<ErrorDetail>) -> ErrorResponder {
+    ErrorResponder::ServiceUnavailable(
+        errors(Status::ServiceUnavailable, details),
+        Header::new("Retry-After", RETRY_AFTER_SECONDS.to_string()),
+    )
 }
 
 #[derive(Responder)]
@@ -83,5 +194,20 @@ pub const fn no_content() -> SuccessResponder<()> {
     SuccessResponder::NoContent(())
 }
 
+#[derive(Responder)]
+pub struct DocumentResponder {
+    body: Vec<u8>,
+    content_type: ContentType,
+}
+
+#[must_use]
+pub fn document(content_type: ContentType, body: impl Into<Vec<u8>>) -> DocumentResponder {
+    DocumentResponder {
+        body: body.into(),
+        content_type,
+    }
+}
+
 pub type HttpResult<T> = Result<SuccessResponder<Data<T>>, ErrorResponder>;
 pub type EmptyHttpResult = Result<SuccessResponder<()>, ErrorResponder>;
+pub type DocumentHttpResult = Result<DocumentResponder, ErrorResponder>;
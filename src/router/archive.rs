@@ -0,0 +1,75 @@
+use crate::archive::{self, ImportFailure};
+use crate::db::DBConnection;
+use crate::models::{Archive, Data, ImportReportOut, MergeStrategy};
+use crate::openapi::{data, schema, Content, Operation};
+use crate::response::{
+    document, internal_server_error, ok, unprocessable_entity, DocumentHttpResult, ErrorDetail,
+    HttpResult,
+};
+use crate::router::read_document;
+
+use rocket::http::ContentType;
+use schemars::gen::SchemaGenerator;
+
+const ARCHIVE_LIMIT_MEBIBYTES: u8 = 64;
+
+#[rocket::get("/export")]
+pub async fn export(connection: DBConnection) -> DocumentHttpResult {
+    let Ok(archive) = connection
+        .run(|connection| archive::export(connection))
+        .await
+    else {
+        return Err(internal_server_error());
+    };
+
+    serde_json::to_string_pretty(&archive).map_or_else(
+        |_| Err(internal_server_error()),
+        |body| Ok(document(ContentType::JSON, body)),
+    )
+}
+
+#[rocket::post("/import?<strategy>", data = "<document>")]
+pub async fn import(
+    strategy: Option<MergeStrategy>,
+    document: rocket::Data<'_>,
+    connection: DBConnection,
+) -> HttpResult<ImportReportOut> {
+    let document = match read_document(document, ARCHIVE_LIMIT_MEBIBYTES).await {
+        Ok(document) => document,
+        Err(error) => return Err(error),
+    };
+
+    let archive = match serde_json::from_str::<Archive>(&document) {
+        Ok(archive) => archive,
+        Err(error) => {
+            return Err(unprocessable_entity(vec![ErrorDetail::new(
+                "invalid_archive",
+                format!("Invalid archive: {error}"),
+            )]))
+        }
+    };
+
+    match connection
+        .run(move |connection| archive::import(&archive, strategy.unwrap_or_default(), connection))
+        .await
+    {
+        Ok(report) => Ok(ok(Data { data: report })),
+        Err(ImportFailure::Invalid(errors)) => Err(unprocessable_entity(errors)),
+        Err(ImportFailure::Database(_)) => Err(internal_server_error()),
+    }
+}
+
+pub fn describe(name: &str, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "export" => Operation::new(
+            "Export every recipe and cart as an archive",
+            200,
+            Content::Json(schema::<Archive>(generator)),
+        ),
+        "import" => Operation::new("Import an archive", 200, data::<ImportReportOut>(generator))
+            .with_request(Content::Json(schema::<Archive>(generator))),
+        _ => return None,
+    };
+
+    Some(operation)
+}
@@ -1,80 +1,205 @@
 use crate::db_utils::{
     delete_from_cart, fetch_one_cart, fetch_one_cart_and_recipes, insert_cart, insert_into_cart,
 };
-use crate::models::{CartWithRecipesOut, Data};
+use crate::exporters;
+use crate::models::{CartWithRecipesOut, Data, ShoppingListOut};
+use crate::openapi::{data, Content, Operation};
 use crate::response::{
-    conflict, created, internal_server_error, no_content, not_found_error, ok, EmptyHttpResult,
-    HttpResult,
+    conflict, created, document, internal_server_error, no_content, not_found_error, ok,
+    DocumentHttpResult, EmptyHttpResult, ErrorDetail, HttpResult,
 };
+use crate::shopping_list;
 
 use crate::db::DBConnection;
 use diesel::result::DatabaseErrorKind;
 use diesel::result::Error as DieselError;
+use rocket::http::ContentType;
+use schemars::gen::SchemaGenerator;
 
 #[rocket::post("/")]
-pub fn create(mut connection: DBConnection) -> HttpResult<CartWithRecipesOut> {
-    insert_cart(&mut connection).map_or_else(
-        |_| Err(internal_server_error()),
-        |cart| Ok(created(Data { data: cart })),
-    )
+pub async fn create(connection: DBConnection) -> HttpResult<CartWithRecipesOut> {
+    connection
+        .run(|connection| insert_cart(connection))
+        .await
+        .map_or_else(
+            |_| Err(internal_server_error()),
+            |cart| Ok(created(Data { data: cart })),
+        )
 }
 
 #[rocket::get("/<cart_id>")]
-pub fn retrieve(cart_id: i32, mut connection: DBConnection) -> HttpResult<CartWithRecipesOut> {
-    match fetch_one_cart_and_recipes(cart_id, &mut connection) {
+pub async fn retrieve(cart_id: i32, connection: DBConnection) -> HttpResult<CartWithRecipesOut> {
+    match connection
+        .run(move |connection| fetch_one_cart_and_recipes(cart_id, connection))
+        .await
+    {
         Ok(cart) => Ok(ok(Data { data: cart })),
-        Err(DieselError::NotFound) => {
-            Err(not_found_error(format!("No cart found with id {cart_id}")))
-        }
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "cart_not_found",
+            format!("No cart found with id {cart_id}"),
+        )),
+        Err(_) => Err(internal_server_error()),
+    }
+}
+
+#[rocket::get("/<cart_id>/export.md")]
+pub async fn export_markdown(cart_id: i32, connection: DBConnection) -> DocumentHttpResult {
+    match connection
+        .run(move |connection| fetch_one_cart_and_recipes(cart_id, connection))
+        .await
+    {
+        Ok(cart) => Ok(document(
+            ContentType::Markdown,
+            exporters::markdown::render_cart(&cart),
+        )),
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "cart_not_found",
+            format!("No cart found with id {cart_id}"),
+        )),
+        Err(_) => Err(internal_server_error()),
+    }
+}
+
+#[rocket::get("/<cart_id>/shopping-list")]
+pub async fn retrieve_shopping_list(
+    cart_id: i32,
+    connection: DBConnection,
+) -> HttpResult<ShoppingListOut> {
+    match connection
+        .run(move |connection| fetch_one_cart_and_recipes(cart_id, connection))
+        .await
+    {
+        Ok(cart) => Ok(ok(Data {
+            data: shopping_list::build(&cart),
+        })),
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "cart_not_found",
+            format!("No cart found with id {cart_id}"),
+        )),
+        Err(_) => Err(internal_server_error()),
+    }
+}
+
+#[rocket::get("/<cart_id>/shopping-list.pdf")]
+pub async fn export_shopping_list_pdf(
+    cart_id: i32,
+    connection: DBConnection,
+) -> DocumentHttpResult {
+    match connection
+        .run(move |connection| fetch_one_cart_and_recipes(cart_id, connection))
+        .await
+    {
+        Ok(cart) => Ok(document(
+            ContentType::PDF,
+            exporters::pdf::render_shopping_list(&cart),
+        )),
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "cart_not_found",
+            format!("No cart found with id {cart_id}"),
+        )),
         Err(_) => Err(internal_server_error()),
     }
 }
 
 #[rocket::delete("/<cart_id>")]
-pub fn delete(cart_id: i32, mut connection: DBConnection) -> EmptyHttpResult {
-    let Ok(deleted_records) = delete_from_cart(cart_id, &mut connection) else {
+pub async fn delete(cart_id: i32, connection: DBConnection) -> EmptyHttpResult {
+    let Ok(deleted_records) = connection
+        .run(move |connection| delete_from_cart(cart_id, connection))
+        .await
+    else {
         return Err(internal_server_error());
     };
 
     if deleted_records == 0 {
-        return Err(not_found_error(format!("No cart found with id {cart_id}")));
+        return Err(not_found_error(
+            "cart_not_found",
+            format!("No cart found with id {cart_id}"),
+        ));
     }
 
     Ok(no_content())
 }
 
 #[rocket::post("/<cart_id>/recipes/<recipe_id>")]
-pub fn add_recipe(
+pub async fn add_recipe(
     cart_id: i32,
     recipe_id: i32,
-    mut connection: DBConnection,
+    connection: DBConnection,
 ) -> HttpResult<CartWithRecipesOut> {
-    match insert_into_cart(cart_id, recipe_id, &mut connection) {
-        Ok(res) => res,
-        Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
-            if fetch_one_cart(cart_id, &mut connection).is_err() {
-                return Err(not_found_error(format!("No cart found with id {cart_id}")));
-            }
+    connection
+        .run(move |connection| {
+            match insert_into_cart(cart_id, recipe_id, connection) {
+                Ok(res) => res,
+                Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                    if fetch_one_cart(cart_id, connection).is_err() {
+                        return Err(not_found_error(
+                            "cart_not_found",
+                            format!("No cart found with id {cart_id}"),
+                        ));
+                    }
 
-            return Err(not_found_error(format!(
-                "No recipe found with id {recipe_id}"
-            )));
-        }
-        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
-            return Err(conflict(format!(
-                "Recipe with id {recipe_id} is already in cart with id {cart_id}"
-            )));
-        }
-        Err(_) => {
-            return Err(internal_server_error());
-        }
-    };
+                    return Err(not_found_error(
+                        "recipe_not_found",
+                        format!("No recipe found with id {recipe_id}"),
+                    ));
+                }
+                Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                    return Err(conflict(ErrorDetail::new(
+                        "recipe_already_in_cart",
+                        format!("Recipe with id {recipe_id} is already in cart with id {cart_id}"),
+                    )));
+                }
+                Err(_) => {
+                    return Err(internal_server_error());
+                }
+            };
 
-    let Ok(cart_with_recipes) = fetch_one_cart_and_recipes(cart_id, &mut connection) else {
-        return Err(internal_server_error());
+            let Ok(cart_with_recipes) = fetch_one_cart_and_recipes(cart_id, connection) else {
+                return Err(internal_server_error());
+            };
+
+            Ok(created(Data {
+                data: cart_with_recipes,
+            }))
+        })
+        .await
+}
+
+pub fn describe(name: &str, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "create" => Operation::new(
+            "Create an empty cart",
+            201,
+            data::<CartWithRecipesOut>(generator),
+        ),
+        "retrieve" => Operation::new(
+            "Retrieve a cart and its recipes",
+            200,
+            data::<CartWithRecipesOut>(generator),
+        ),
+        "export_markdown" => Operation::new(
+            "Export a cart as Markdown",
+            200,
+            Content::Documents(&["text/markdown"]),
+        ),
+        "retrieve_shopping_list" => Operation::new(
+            "Retrieve the shopping list of a cart",
+            200,
+            data::<ShoppingListOut>(generator),
+        ),
+        "export_shopping_list_pdf" => Operation::new(
+            "Export the shopping list of a cart as PDF",
+            200,
+            Content::Documents(&["application/pdf"]),
+        ),
+        "delete" => Operation::new("Delete a cart", 204, Content::Empty),
+        "add_recipe" => Operation::new(
+            "Add a recipe to a cart",
+            201,
+            data::<CartWithRecipesOut>(generator),
+        ),
+        _ => return None,
     };
 
-    Ok(created(Data {
-        data: cart_with_recipes,
-    }))
+    Some(operation)
 }
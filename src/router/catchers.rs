@@ -0,0 +1,59 @@
+use crate::response::{
+    service_unavailable as service_unavailable_error, status_error, ErrorDetail, ErrorResponder,
+    Errors,
+};
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::Request;
+
+#[rocket::catch(400)]
+#[must_use]
+pub fn bad_request() -> (Status, Json<Errors>) {
+    status_error(Status::BadRequest, "Malformed request".to_owned())
+}
+
+#[rocket::catch(404)]
+#[must_use]
+pub fn not_found(request: &Request<'_>) -> (Status, Json<Errors>) {
+    status_error(
+        Status::NotFound,
+        format!("No route found for {} {}", request.method(), request.uri()),
+    )
+}
+
+#[rocket::catch(422)]
+#[must_use]
+pub fn unprocessable_entity(request: &Request<'_>) -> (Status, Json<Errors>) {
+    status_error(
+        Status::UnprocessableEntity,
+        format!("Invalid request to {} {}", request.method(), request.uri()),
+    )
+}
+
+#[rocket::catch(500)]
+#[must_use]
+pub fn internal_server_error() -> (Status, Json<Errors>) {
+    status_error(
+        Status::InternalServerError,
+        "Internal server error".to_owned(),
+    )
+}
+
+#[rocket::catch(503)]
+#[must_use]
+pub fn service_unavailable() -> ErrorResponder {
+    service_unavailable_error(vec![ErrorDetail::new(
+        "database_unavailable",
+        "No database connection available, retry later".to_owned(),
+    )])
+}
+
+#[rocket::catch(default)]
+#[must_use]
+pub fn default(status: Status, _request: &Request<'_>) -> (Status, Json<Errors>) {
+    status_error(
+        status,
+        status.reason().unwrap_or("Unknown error").to_owned(),
+    )
+}
@@ -0,0 +1,79 @@
+use crate::openapi::{Content, Operation, Specification};
+
+use rocket::http::ContentType;
+use rocket::response::content::RawHtml;
+use rocket::serde::json::{Json, Value};
+use rocket::State;
+use schemars::gen::SchemaGenerator;
+
+const SWAGGER_UI_SCRIPT: &str = include_str!("../../static/swagger-ui/swagger-ui-bundle.js");
+const SWAGGER_UI_STYLESHEET: &str = include_str!("../../static/swagger-ui/swagger-ui.css");
+
+const DOCS_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>Cooking book API</title>
+    <link rel="stylesheet" href="/api/docs/swagger-ui.css">
+  </head>
+  <body>
+    <div id="swagger-ui"></div>
+    <script src="/api/docs/swagger-ui-bundle.js"></script>
+    <script>
+      window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+    </script>
+  </body>
+</html>
+"##;
+
+#[rocket::get("/openapi.json")]
+#[must_use]
+pub fn specification(specification: &State<Specification>) -> Json<Value> {
+    Json(specification.0.clone())
+}
+
+#[rocket::get("/docs")]
+#[must_use]
+pub const fn docs() -> RawHtml<&'static str> {
+    RawHtml(DOCS_PAGE)
+}
+
+#[rocket::get("/docs/swagger-ui-bundle.js")]
+#[must_use]
+pub const fn script() -> (ContentType, &'static str) {
+    (ContentType::JavaScript, SWAGGER_UI_SCRIPT)
+}
+
+#[rocket::get("/docs/swagger-ui.css")]
+#[must_use]
+pub const fn stylesheet() -> (ContentType, &'static str) {
+    (ContentType::CSS, SWAGGER_UI_STYLESHEET)
+}
+
+pub fn describe(name: &str, _generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "specification" => Operation::new(
+            "Describe the API as an OpenAPI document",
+            200,
+            Content::Documents(&["application/json"]),
+        ),
+        "docs" => Operation::new(
+            "Browse the API documentation",
+            200,
+            Content::Documents(&["text/html"]),
+        ),
+        "script" => Operation::new(
+            "Serve the documentation script",
+            200,
+            Content::Documents(&["text/javascript"]),
+        ),
+        "stylesheet" => Operation::new(
+            "Serve the documentation stylesheet",
+            200,
+            Content::Documents(&["text/css"]),
+        ),
+        _ => return None,
+    };
+
+    Some(operation)
+}
@@ -0,0 +1,39 @@
+use rocket::http::{ContentType, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+
+#[derive(Clone, Copy)]
+pub enum TextFormat {
+    Markdown,
+    Plain,
+}
+
+impl TextFormat {
+    #[must_use]
+    pub const fn content_type(self) -> ContentType {
+        match self {
+            Self::Markdown => ContentType::Markdown,
+            Self::Plain => ContentType::Plain,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for TextFormat {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(accept) = request.accept() else {
+            return Outcome::Forward(Status::NotFound);
+        };
+
+        let media_type = accept.preferred().media_type();
+
+        if media_type.is_markdown() {
+            Outcome::Success(Self::Markdown)
+        } else if media_type.is_plain() {
+            Outcome::Success(Self::Plain)
+        } else {
+            Outcome::Forward(Status::NotFound)
+        }
+    }
+}
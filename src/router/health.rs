@@ -0,0 +1,102 @@
+use crate::db::{run_blocking, schema_status, Pool};
+use crate::models::{Data, HealthOut, PoolStatsOut, ReadinessOut};
+use crate::openapi::{data, Operation};
+use crate::response::{ok, service_unavailable, ErrorDetail, HttpResult};
+
+use rocket::State;
+use schemars::gen::SchemaGenerator;
+use std::time::Duration;
+
+const READY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[rocket::get("/health")]
+pub fn health() -> HttpResult<HealthOut> {
+    Ok(ok(Data {
+        data: HealthOut {
+            status: "ok".to_owned(),
+        },
+    }))
+}
+
+fn readiness(pool: &Pool) -> HttpResult<ReadinessOut> {
+    let mut connection = match pool.get_timeout(READY_TIMEOUT) {
+        Ok(connection) => connection,
+        Err(error) => {
+            return Err(service_unavailable(vec![ErrorDetail::new(
+                "database_unavailable",
+                format!("No database connection available: {error}"),
+            )]))
+        }
+    };
+
+    let status = match schema_status(&mut connection) {
+        Ok(status) => status,
+        Err(error) => {
+            return Err(service_unavailable(vec![ErrorDetail::new(
+                "schema_unreadable",
+                format!("Cannot read the database schema: {error}"),
+            )]))
+        }
+    };
+
+    drop(connection);
+
+    let mut errors = Vec::new();
+
+    if !status.pending.is_empty() {
+        errors.push(ErrorDetail::new(
+            "pending_migrations",
+            format!("Pending migrations: {}", status.pending.join(", ")),
+        ));
+    }
+
+    if !status.unknown.is_empty() {
+        errors.push(ErrorDetail::new(
+            "unknown_migrations",
+            format!("Unknown migrations: {}", status.unknown.join(", ")),
+        ));
+    }
+
+    if !errors.is_empty() {
+        return Err(service_unavailable(errors));
+    }
+
+    let state = pool.state();
+
+    Ok(ok(Data {
+        data: ReadinessOut {
+            status: "ready".to_owned(),
+            schema_version: status.version,
+            pool: PoolStatsOut {
+                max_size: pool.max_size(),
+                connections: state.connections,
+                idle_connections: state.idle_connections,
+            },
+        },
+    }))
+}
+
+#[rocket::get("/ready")]
+pub async fn ready(pool: &State<Pool>) -> HttpResult<ReadinessOut> {
+    let pool = pool.inner().clone();
+
+    run_blocking(move || readiness(&pool)).await
+}
+
+pub fn describe(name: &str, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "health" => Operation::new(
+            "Report that the service is up",
+            200,
+            data::<HealthOut>(generator),
+        ),
+        "ready" => Operation::new(
+            "Report whether the database is ready",
+            200,
+            data::<ReadinessOut>(generator),
+        ),
+        _ => return None,
+    };
+
+    Some(operation)
+}
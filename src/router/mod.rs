@@ -1,2 +1,34 @@
+pub mod archive;
 pub mod carts;
+pub mod catchers;
+pub mod docs;
+pub mod guards;
+pub mod health;
+pub mod params;
 pub mod recipes;
+pub mod tags;
+
+use crate::response::{unprocessable_entity, ErrorDetail, ErrorResponder};
+
+use rocket::data::ToByteUnit;
+
+pub async fn read_document(
+    document: rocket::Data<'_>,
+    limit_mebibytes: u8,
+) -> Result<String, ErrorResponder> {
+    match document
+        .open(limit_mebibytes.mebibytes())
+        .into_string()
+        .await
+    {
+        Ok(document) if document.is_complete() => Ok(document.into_inner()),
+        Ok(_) => Err(unprocessable_entity(vec![ErrorDetail::new(
+            "document_too_large",
+            format!("Document is larger than {limit_mebibytes} MiB"),
+        )])),
+        Err(_) => Err(unprocessable_entity(vec![ErrorDetail::new(
+            "invalid_encoding",
+            "Document is not valid UTF-8".to_owned(),
+        )])),
+    }
+}
@@ -0,0 +1,21 @@
+use rocket::request::FromParam;
+
+#[derive(Clone, Copy)]
+pub struct IdWithExtension<'a> {
+    pub id: i32,
+    pub extension: &'a str,
+}
+
+impl<'a> FromParam<'a> for IdWithExtension<'a> {
+    type Error = &'a str;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        let Some((id, extension)) = param.rsplit_once('.') else {
+            return Err(param);
+        };
+
+        id.parse::<i32>()
+            .map(|id| Self { id, extension })
+            .map_err(|_| param)
+    }
+}
@@ -1,45 +1,497 @@
 use crate::db::DBConnection;
-use crate::db_utils::{fetch_all_recipes, fetch_one_recipe, insert_recipe};
-use crate::models::{Data, RecipeIn, RecipeWithIngredientsOut};
-use crate::response::{conflict, created, internal_server_error, not_found_error, ok, HttpResult};
+use crate::db_utils::{
+    delete_step_at_position, fetch_all_recipes, fetch_one_recipe, fetch_recipe_id_by_name,
+    fetch_recipes_with_tag, insert_recipe, insert_step_at_position, move_step_to_position,
+};
+use crate::exporters;
+use crate::importers::{self, markdown, ImportError};
+use crate::models::{
+    CollectionFormat, Data, ImportFormat, RecipeIn, RecipeSort, RecipeWithIngredientsOut, StepIn,
+    StepPositionIn,
+};
+use crate::openapi::{data, schema, Content, Operation};
+use crate::response::{
+    conflict, created, document, internal_server_error, no_content, not_found_error, ok,
+    unprocessable_entity, DocumentHttpResult, DocumentResponder, EmptyHttpResult, ErrorDetail,
+    ErrorResponder, HttpResult,
+};
+use crate::router::guards::TextFormat;
+use crate::router::params::IdWithExtension;
+use crate::router::read_document;
+use crate::validation::{validate_recipe, validate_step};
 
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
-use rocket::serde::json::Json;
+use diesel::Connection;
+use rocket::http::ContentType;
+use rocket::serde::json::{json, Json};
+use schemars::gen::SchemaGenerator;
 
-#[rocket::post("/", data = "<recipe_in>")]
-pub fn create(
-    recipe_in: Json<RecipeIn>,
-    mut connection: DBConnection,
+const IMPORT_LIMIT_MEBIBYTES: u8 = 5;
+
+fn store_recipe(
+    recipe_in: &RecipeIn,
+    connection: &mut DBConnection,
 ) -> HttpResult<RecipeWithIngredientsOut> {
-    let recipe_inner = recipe_in.into_inner();
+    if let Err(error_messages) = validate_recipe(recipe_in) {
+        return Err(unprocessable_entity(error_messages));
+    }
 
-    match insert_recipe(&recipe_inner, &mut connection) {
+    match insert_recipe(recipe_in, connection) {
         Ok(recipe) => Ok(created(Data { data: recipe })),
-        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(conflict(
-            format!("Recipe already exists: {}", recipe_inner.name),
-        )),
+        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+            Err(duplicate_name(recipe_in, "/name".to_owned(), connection))
+        }
+        Err(_) => Err(internal_server_error()),
+    }
+}
+
+fn store_recipes(
+    recipes_in: &[RecipeIn],
+    connection: &mut DBConnection,
+) -> HttpResult<Vec<RecipeWithIngredientsOut>> {
+    let error_messages: Vec<ErrorDetail> = recipes_in
+        .iter()
+        .enumerate()
+        .flat_map(|(index, recipe_in)| {
+            validate_recipe(recipe_in)
+                .err()
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |error| error.within("recipes", index))
+        })
+        .collect();
+
+    if !error_messages.is_empty() {
+        return Err(unprocessable_entity(error_messages));
+    }
+
+    let mut stored = 0;
+
+    let inserted = connection.transaction(|connection| {
+        recipes_in
+            .iter()
+            .map(|recipe_in| {
+                let recipe = insert_recipe(recipe_in, connection);
+
+                if recipe.is_ok() {
+                    stored += 1;
+                }
+
+                recipe
+            })
+            .collect::<Result<Vec<RecipeWithIngredientsOut>, DieselError>>()
+    });
+
+    match inserted {
+        Ok(recipes) => Ok(created(Data { data: recipes })),
+        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+            Err(duplicate_name(
+                &recipes_in[stored],
+                format!("/recipes/{stored}/name"),
+                connection,
+            ))
+        }
         Err(_) => Err(internal_server_error()),
     }
 }
 
-#[rocket::get("/")]
-pub fn retrieve_all(mut connection: DBConnection) -> HttpResult<Vec<RecipeWithIngredientsOut>> {
-    fetch_all_recipes(&mut connection).map_or_else(
-        |_| Err(internal_server_error()),
-        |recipes| Ok(ok(Data { data: recipes })),
+fn duplicate_name(
+    recipe_in: &RecipeIn,
+    source: String,
+    connection: &mut DBConnection,
+) -> ErrorResponder {
+    let detail = ErrorDetail::new(
+        "duplicate_recipe_name",
+        format!("Recipe already exists: {}", recipe_in.name.trim()),
     )
+    .at(source);
+
+    match fetch_recipe_id_by_name(&recipe_in.name, connection) {
+        Ok(Some(existing_id)) => conflict(detail.with_meta(json!({ "recipe_id": existing_id }))),
+        Ok(None) => conflict(detail),
+        Err(_) => internal_server_error(),
+    }
+}
+
+fn import_error(error: &ImportError) -> ErrorResponder {
+    unprocessable_entity(vec![ErrorDetail::new(error.code(), error.to_string())])
+}
+
+#[rocket::post("/", data = "<recipe_in>", rank = 2)]
+pub async fn create(
+    recipe_in: Json<RecipeIn>,
+    connection: DBConnection,
+) -> HttpResult<RecipeWithIngredientsOut> {
+    let recipe_in = recipe_in.into_inner();
+
+    connection
+        .run(move |connection| store_recipe(&recipe_in, connection))
+        .await
 }
 
-#[rocket::get("/<recipe_id>")]
-pub fn retrieve(
+#[rocket::post("/", format = "text/markdown", data = "<document>", rank = 1)]
+pub async fn create_from_markdown(
+    document: rocket::Data<'_>,
+    connection: DBConnection,
+) -> HttpResult<RecipeWithIngredientsOut> {
+    let document = match read_document(document, IMPORT_LIMIT_MEBIBYTES).await {
+        Ok(document) => document,
+        Err(error) => return Err(error),
+    };
+
+    match markdown::parse(&document) {
+        Ok(recipe_in) => {
+            connection
+                .run(move |connection| store_recipe(&recipe_in, connection))
+                .await
+        }
+        Err(error) => Err(import_error(&error)),
+    }
+}
+
+#[rocket::post("/import?<format>", data = "<document>", rank = 1)]
+pub async fn import_collection(
+    format: CollectionFormat,
+    document: rocket::Data<'_>,
+    connection: DBConnection,
+) -> HttpResult<Vec<RecipeWithIngredientsOut>> {
+    let document = match read_document(document, IMPORT_LIMIT_MEBIBYTES).await {
+        Ok(document) => document,
+        Err(error) => return Err(error),
+    };
+
+    match importers::parse(format.into(), &document, None) {
+        Ok(recipes) => {
+            connection
+                .run(move |connection| store_recipes(&recipes, connection))
+                .await
+        }
+        Err(error) => Err(import_error(&error)),
+    }
+}
+
+#[rocket::post("/import?<format>", data = "<document>", rank = 2)]
+pub async fn import(
+    format: Option<ImportFormat>,
+    document: rocket::Data<'_>,
+    connection: DBConnection,
+) -> HttpResult<RecipeWithIngredientsOut> {
+    let document = match read_document(document, IMPORT_LIMIT_MEBIBYTES).await {
+        Ok(document) => document,
+        Err(error) => return Err(error),
+    };
+
+    match importers::parse(format.unwrap_or_default(), &document, None) {
+        Ok(mut recipes) if recipes.len() == 1 => {
+            let recipe_in = recipes.remove(0);
+
+            connection
+                .run(move |connection| store_recipe(&recipe_in, connection))
+                .await
+        }
+        Ok(recipes) => Err(import_error(&ImportError::UnexpectedRecipeCount(
+            recipes.len(),
+        ))),
+        Err(error) => Err(import_error(&error)),
+    }
+}
+
+#[rocket::get("/?<tag>&<sort>")]
+pub async fn retrieve_all(
+    tag: Option<&str>,
+    sort: Option<RecipeSort>,
+    connection: DBConnection,
+) -> HttpResult<Vec<RecipeWithIngredientsOut>> {
+    let tag = tag.map(str::to_owned);
+
+    let recipes = connection
+        .run(move |connection| match tag {
+            Some(tag) => fetch_recipes_with_tag(&tag, connection),
+            None => fetch_all_recipes(connection),
+        })
+        .await;
+
+    let Ok(mut recipes) = recipes else {
+        return Err(internal_server_error());
+    };
+
+    match sort {
+        Some(RecipeSort::Name) => recipes.sort_by(|a, b| a.name.cmp(&b.name)),
+        Some(RecipeSort::TotalTime) => {
+            recipes.sort_by_key(|recipe| {
+                recipe
+                    .active_time_minutes
+                    .saturating_add(recipe.passive_time_minutes)
+            });
+        }
+        None => (),
+    };
+
+    Ok(ok(Data { data: recipes }))
+}
+
+#[rocket::get("/<recipe_id>", rank = 2)]
+pub async fn retrieve(
     recipe_id: i32,
-    mut connection: DBConnection,
+    connection: DBConnection,
 ) -> HttpResult<RecipeWithIngredientsOut> {
-    match fetch_one_recipe(recipe_id, &mut connection) {
+    match connection
+        .run(move |connection| fetch_one_recipe(recipe_id, connection))
+        .await
+    {
         Ok(recipe) => Ok(ok(Data { data: recipe })),
-        Err(DieselError::NotFound) => Err(not_found_error(format!(
-            "No recipe found with id {recipe_id}"
-        ))),
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "recipe_not_found",
+            format!("No recipe found with id {recipe_id}"),
+        )),
+        Err(_) => Err(internal_server_error()),
+    }
+}
+
+fn fetch_recipe_document(
+    recipe_id: i32,
+    connection: &mut DBConnection,
+) -> Result<RecipeWithIngredientsOut, ErrorResponder> {
+    match fetch_one_recipe(recipe_id, connection) {
+        Ok(recipe) => Ok(recipe),
+        Err(DieselError::NotFound) => Err(not_found_error(
+            "recipe_not_found",
+            format!("No recipe found with id {recipe_id}"),
+        )),
         Err(_) => Err(internal_server_error()),
     }
 }
+
+fn render_text(recipe: &RecipeWithIngredientsOut, format: TextFormat) -> DocumentResponder {
+    let body = match format {
+        TextFormat::Markdown => exporters::markdown::render(recipe),
+        TextFormat::Plain => exporters::plain_text::render(recipe),
+    };
+
+    document(format.content_type(), body)
+}
+
+#[rocket::get("/<recipe_id>", rank = 1)]
+pub async fn retrieve_text(
+    recipe_id: i32,
+    format: TextFormat,
+    connection: DBConnection,
+) -> DocumentHttpResult {
+    let recipe = match connection
+        .run(move |connection| fetch_recipe_document(recipe_id, connection))
+        .await
+    {
+        Ok(recipe) => recipe,
+        Err(error) => return Err(error),
+    };
+
+    Ok(render_text(&recipe, format))
+}
+
+#[rocket::get("/<recipe_file>", rank = 3)]
+pub async fn retrieve_file(
+    recipe_file: IdWithExtension<'_>,
+    connection: DBConnection,
+) -> DocumentHttpResult {
+    let recipe_id = recipe_file.id;
+
+    let recipe = match connection
+        .run(move |connection| fetch_recipe_document(recipe_id, connection))
+        .await
+    {
+        Ok(recipe) => recipe,
+        Err(error) => return Err(error),
+    };
+
+    match recipe_file.extension {
+        "jsonld" => Ok(document(
+            ContentType::new("application", "ld+json"),
+            exporters::jsonld::render(&recipe).to_string(),
+        )),
+        "md" => Ok(render_text(&recipe, TextFormat::Markdown)),
+        "pdf" => Ok(document(
+            ContentType::PDF,
+            exporters::pdf::render_recipe(&recipe),
+        )),
+        "txt" => Ok(render_text(&recipe, TextFormat::Plain)),
+        extension => Err(not_found_error(
+            "unsupported_format",
+            format!("Unsupported recipe format: {extension}"),
+        )),
+    }
+}
+
+fn step_not_found(recipe_id: i32, position: i32, connection: &mut DBConnection) -> ErrorResponder {
+    match fetch_one_recipe(recipe_id, connection) {
+        Ok(_) => not_found_error(
+            "step_not_found",
+            format!("No step found at position {position} in recipe with id {recipe_id}"),
+        ),
+        Err(DieselError::NotFound) => not_found_error(
+            "recipe_not_found",
+            format!("No recipe found with id {recipe_id}"),
+        ),
+        Err(_) => internal_server_error(),
+    }
+}
+
+#[rocket::post("/<recipe_id>/steps/<position>", data = "<step_in>")]
+pub async fn create_step(
+    recipe_id: i32,
+    position: i32,
+    step_in: Json<StepIn>,
+    connection: DBConnection,
+) -> HttpResult<RecipeWithIngredientsOut> {
+    let step_inner = step_in.into_inner();
+
+    if let Some(error) = validate_step(
+        "description",
+        "/description".to_owned(),
+        &step_inner.description,
+    ) {
+        return Err(unprocessable_entity(vec![error]));
+    }
+
+    connection
+        .run(move |connection| {
+            match insert_step_at_position(recipe_id, position, &step_inner.description, connection)
+            {
+                Ok(()) => (),
+                Err(
+                    DieselError::NotFound
+                    | DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _),
+                ) => return Err(step_not_found(recipe_id, position, connection)),
+                Err(_) => return Err(internal_server_error()),
+            };
+
+            fetch_one_recipe(recipe_id, connection).map_or_else(
+                |_| Err(internal_server_error()),
+                |recipe| Ok(created(Data { data: recipe })),
+            )
+        })
+        .await
+}
+
+#[rocket::put("/<recipe_id>/steps/<position>", data = "<step_position_in>")]
+pub async fn move_step(
+    recipe_id: i32,
+    position: i32,
+    step_position_in: Json<StepPositionIn>,
+    connection: DBConnection,
+) -> HttpResult<RecipeWithIngredientsOut> {
+    let new_position = step_position_in.into_inner().position;
+
+    connection
+        .run(move |connection| {
+            match move_step_to_position(recipe_id, position, new_position, connection) {
+                Ok(()) => (),
+                Err(DieselError::NotFound) => {
+                    let missing_position = match fetch_one_recipe(recipe_id, connection) {
+                        Ok(recipe)
+                            if usize::try_from(position).is_ok_and(|p| p < recipe.steps.len()) =>
+                        {
+                            new_position
+                        }
+                        _ => position,
+                    };
+
+                    return Err(step_not_found(recipe_id, missing_position, connection));
+                }
+                Err(_) => return Err(internal_server_error()),
+            };
+
+            fetch_one_recipe(recipe_id, connection).map_or_else(
+                |_| Err(internal_server_error()),
+                |recipe| Ok(ok(Data { data: recipe })),
+            )
+        })
+        .await
+}
+
+#[rocket::delete("/<recipe_id>/steps/<position>")]
+pub async fn delete_step(
+    recipe_id: i32,
+    position: i32,
+    connection: DBConnection,
+) -> EmptyHttpResult {
+    connection
+        .run(
+            move |connection| match delete_step_at_position(recipe_id, position, connection) {
+                Ok(()) => Ok(no_content()),
+                Err(DieselError::NotFound) => Err(step_not_found(recipe_id, position, connection)),
+                Err(_) => Err(internal_server_error()),
+            },
+        )
+        .await
+}
+
+pub fn describe(name: &str, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "create" => Operation::new(
+            "Create a recipe",
+            201,
+            data::<RecipeWithIngredientsOut>(generator),
+        )
+        .with_request(Content::Json(schema::<RecipeIn>(generator))),
+        "create_from_markdown" => Operation::new(
+            "Create a recipe",
+            201,
+            data::<RecipeWithIngredientsOut>(generator),
+        )
+        .with_request(Content::Documents(&["text/markdown"])),
+        "import" => Operation::new(
+            "Import recipes from another format",
+            201,
+            data::<RecipeWithIngredientsOut>(generator),
+        )
+        .with_request(Content::Documents(&["*/*"])),
+        "import_collection" => Operation::new(
+            "Import recipes from another format",
+            201,
+            data::<Vec<RecipeWithIngredientsOut>>(generator),
+        )
+        .with_request(Content::Documents(&["*/*"])),
+        "retrieve_all" => Operation::new(
+            "List recipes",
+            200,
+            data::<Vec<RecipeWithIngredientsOut>>(generator),
+        ),
+        "retrieve" => Operation::new(
+            "Retrieve a recipe",
+            200,
+            data::<RecipeWithIngredientsOut>(generator),
+        ),
+        "retrieve_text" => Operation::new(
+            "Retrieve a recipe",
+            200,
+            Content::Documents(&["text/markdown", "text/plain"]),
+        ),
+        "retrieve_file" => Operation::new(
+            "Download a recipe as a file",
+            200,
+            Content::Documents(&[
+                "application/ld+json",
+                "application/pdf",
+                "text/markdown",
+                "text/plain",
+            ]),
+        )
+        .at("/api/recipes/{recipe_id}.{extension}"),
+        "create_step" => Operation::new(
+            "Insert a step at a position",
+            201,
+            data::<RecipeWithIngredientsOut>(generator),
+        )
+        .with_request(Content::Json(schema::<StepIn>(generator))),
+        "move_step" => Operation::new(
+            "Move a step to another position",
+            200,
+            data::<RecipeWithIngredientsOut>(generator),
+        )
+        .with_request(Content::Json(schema::<StepPositionIn>(generator))),
+        "delete_step" => Operation::new("Delete a step", 204, Content::Empty),
+        _ => return None,
+    };
+
+    Some(operation)
+}
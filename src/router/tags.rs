@@ -0,0 +1,127 @@
+use crate::db::DBConnection;
+use crate::db_utils::{fetch_all_tags, fetch_one_tag, merge_tags, rename_tag};
+use crate::models::{Data, TagIn, TagOut};
+use crate::openapi::{data, schema, Content, Operation};
+use crate::response::{
+    conflict, internal_server_error, not_found_error, ok, unprocessable_entity, ErrorDetail,
+    HttpResult,
+};
+use crate::validation::validate_tag;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use rocket::serde::json::Json;
+use schemars::gen::SchemaGenerator;
+
+#[rocket::get("/")]
+pub async fn retrieve_all(connection: DBConnection) -> HttpResult<Vec<TagOut>> {
+    connection
+        .run(|connection| fetch_all_tags(connection))
+        .await
+        .map_or_else(
+            |_| Err(internal_server_error()),
+            |tags| Ok(ok(Data { data: tags })),
+        )
+}
+
+#[rocket::put("/<tag_id>", data = "<new_tag>")]
+pub async fn rename(
+    tag_id: i32,
+    new_tag: Json<TagIn>,
+    connection: DBConnection,
+) -> HttpResult<TagOut> {
+    let tag_inner = new_tag.into_inner();
+
+    if tag_inner.name.trim().is_empty() {
+        return Err(unprocessable_entity(vec![ErrorDetail::new(
+            "blank_name",
+            "name must not be blank".to_owned(),
+        )
+        .at("/name".to_owned())]));
+    }
+
+    if let Some(error) = validate_tag("name", "/name".to_owned(), &tag_inner.name) {
+        return Err(unprocessable_entity(vec![error]));
+    }
+
+    connection
+        .run(move |connection| {
+            match rename_tag(tag_id, &tag_inner.name, connection) {
+                Ok(0) => {
+                    return Err(not_found_error(
+                        "tag_not_found",
+                        format!("No tag found with id {tag_id}"),
+                    ))
+                }
+                Ok(_) => (),
+                Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                    return Err(conflict(ErrorDetail::new(
+                        "duplicate_tag_name",
+                        format!("Tag already exists: {}", tag_inner.name.trim()),
+                    )));
+                }
+                Err(_) => return Err(internal_server_error()),
+            };
+
+            fetch_one_tag(tag_id, connection).map_or_else(
+                |_| Err(internal_server_error()),
+                |tag| Ok(ok(Data { data: tag })),
+            )
+        })
+        .await
+}
+
+#[rocket::post("/<tag_id>/merge/<target_tag_id>")]
+pub async fn merge(
+    tag_id: i32,
+    target_tag_id: i32,
+    connection: DBConnection,
+) -> HttpResult<TagOut> {
+    if tag_id == target_tag_id {
+        return Err(unprocessable_entity(vec![ErrorDetail::new(
+            "merge_into_itself",
+            format!("Cannot merge tag with id {tag_id} into itself"),
+        )]));
+    }
+
+    connection
+        .run(move |connection| {
+            match merge_tags(tag_id, target_tag_id, connection) {
+                Ok(()) => (),
+                Err(DieselError::NotFound) => {
+                    let missing_tag_id = if fetch_one_tag(tag_id, connection).is_err() {
+                        tag_id
+                    } else {
+                        target_tag_id
+                    };
+
+                    return Err(not_found_error(
+                        "tag_not_found",
+                        format!("No tag found with id {missing_tag_id}"),
+                    ));
+                }
+                Err(_) => return Err(internal_server_error()),
+            };
+
+            fetch_one_tag(target_tag_id, connection).map_or_else(
+                |_| Err(internal_server_error()),
+                |tag| Ok(ok(Data { data: tag })),
+            )
+        })
+        .await
+}
+
+pub fn describe(name: &str, generator: &mut SchemaGenerator) -> Option<Operation> {
+    let operation = match name {
+        "retrieve_all" => Operation::new("List tags", 200, data::<Vec<TagOut>>(generator)),
+        "rename" => Operation::new("Rename a tag", 200, data::<TagOut>(generator))
+            .with_request(Content::Json(schema::<TagIn>(generator))),
+        "merge" => Operation::new(
+            "Merge a tag into another one",
+            200,
+            data::<TagOut>(generator),
+        ),
+        _ => return None,
+    };
+
+    Some(operation)
+}
@@ -25,10 +25,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recipe_tags (recipe_id, tag_id) {
+        recipe_id -> Integer,
+        tag_id -> Integer,
+    }
+}
+
 diesel::table! {
     recipes (id) {
         id -> Integer,
         name -> Text,
+        description -> Nullable<Text>,
+        prep_time_minutes -> Nullable<Integer>,
+        cook_time_minutes -> Nullable<Integer>,
+        difficulty -> Nullable<Text>,
+        source_url -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        normalized_name -> Text,
+    }
+}
+
+diesel::table! {
+    step_ingredients (step_id, ingredient_id) {
+        step_id -> Integer,
+        ingredient_id -> Integer,
     }
 }
 
@@ -37,12 +58,37 @@ diesel::table! {
         id -> Integer,
         recipe_id -> Integer,
         description -> Text,
+        position -> Integer,
+        duration_minutes -> Nullable<Integer>,
+        temperature_celsius -> Nullable<Integer>,
+        passive -> Bool,
+    }
+}
+
+diesel::table! {
+    tags (id) {
+        id -> Integer,
+        name -> Text,
+        slug -> Text,
     }
 }
 
 diesel::joinable!(cart_recipes -> carts (cart_id));
 diesel::joinable!(cart_recipes -> recipes (recipe_id));
 diesel::joinable!(ingredients -> recipes (recipe_id));
+diesel::joinable!(recipe_tags -> recipes (recipe_id));
+diesel::joinable!(recipe_tags -> tags (tag_id));
+diesel::joinable!(step_ingredients -> ingredients (ingredient_id));
+diesel::joinable!(step_ingredients -> steps (step_id));
 diesel::joinable!(steps -> recipes (recipe_id));
 
-diesel::allow_tables_to_appear_in_same_query!(cart_recipes, carts, ingredients, recipes, steps,);
+diesel::allow_tables_to_appear_in_same_query!(
+    cart_recipes,
+    carts,
+    ingredients,
+    recipe_tags,
+    recipes,
+    step_ingredients,
+    steps,
+    tags,
+);
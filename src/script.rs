@@ -1,61 +1,79 @@
 use diesel::result::DatabaseErrorKind;
 use diesel::result::Error as DieselError;
 
-use crate::db::DBConnection;
-use crate::db_utils::insert_recipe;
-use crate::models::RecipeIn;
-
-pub fn create_recipes(connection: &mut DBConnection) {
-    let recipes = [
-        RecipeIn {
-            name: "Saucisses aux lentilles".to_owned(),
-            ingredients: vec![
-                "350 g de Lentilles vertes".to_owned(),
-                "300 g de saucisses de Montb\u{e9}liard".to_owned(),
-                "200 g de lardons fum\u{e9}s".to_owned(),
-                "1 oignon".to_owned(),
-                "2 gousse d'ail".to_owned(),
-                "2 feuille de laurier".to_owned(),
-            ],
-            steps: vec![
-                "Eplucher et \u{e9}mincer l'oignon. Peler les gousses d'ail.".to_owned(),
-                "Dans une cocotte, mettre les lentilles, les saucisses, les lardons, l'oignon \u{e9}minces, les gousses d'ail et les feuilles de laurier. Ajouter 70 cl d'eau, saler et poivrer.".to_owned(),
-                "Faire cuire pendant 40 minutes sur feu moyen \u{e0} couvert. Servir bien chaud.".to_owned()
-            ],
-        },
-        RecipeIn {
-            name: "Gratin de gnocchi au saumon et \u{e9}pinards".to_owned(),
-            ingredients:vec! [
-                "400g de gnocchi".to_owned(),
-                "300g d'\u{e9}pinards surgel\u{e9}s".to_owned(),
-                "200g de pav\u{e9} de saumon".to_owned(),
-                "150 g parmesan r\u{e2}p\u{e9}".to_owned(),
-                "0.5litre de lait".to_owned(),
-                "30.0 g de farine".to_owned(),
-                "30g de beurre".to_owned()
-            ],
-            steps: vec![
-                "Faire cuire les gnocchi dans une grande casserole d'eau bouillante sal\u{e9}e en suivant les indications sur le sachet. ".to_owned(),
-                "Dans une casserole, faire cuire les \u{e9}pinards avec un peu de beurre pendant 10 minutes.".to_owned(),
-                "D\u{e9}couper les pav\u{e9}s de saumon en d\u{e9}s. Pr\u{e9}chauffer le four \u{e0} 180\u{b0}C.".to_owned(),
-                "Pr\u{e9}parer la b\u{e9}chamel en faisant fondre le beurre coup\u{e9} en d\u{e9}s dans une casserole. Ajouter la farine en remuant. Verser le lait progressivement en continuant de remuer jusqu'\u{e0} ce que la cr\u{e8}me \u{e9}paississe. Ajouter le parmesan, saler et poivrer.".to_owned(),
-                "D\u{e9}poser les gnocchi \u{e9}goutt\u{e9}s dans le fond d'un plat \u{e0} gratin. Ajoutez la moiti\u{e9} de la b\u{e9}chamel. Recouvrir de saumon et d'\u{e9}pinards et ajouter le reste de b\u{e9}chamel. Enfourner pour 20 minutes \u{e0} 180\u{b0}C. Servir aussit\u{f4}t.".to_owned(),
-            ]
-        },
-        RecipeIn {
-            name: "Tapenade : la meilleure recette".to_owned(),
-            ingredients: vec![
-                "200g d'olive noir".to_owned(),
-                "8 c\u{e2}pres".to_owned(),
-                "5filet anchois \u{e0} l'huile".to_owned(),
-                "1 gousse d'ail".to_owned(),
-            ],
-            steps: vec![
-                "Hacher finement la gousse d\u{2019}ail.".to_owned(),
-                "Mettre dans le bol d\u{2019}un mixeur les filets d\u{2019}anchois, les c\u{e2}pres, la gousse d\u{2019}ail hach\u{e9}e, les olives noires et l\u{2019}huile d\u{2019}olive et mixer assez fin.".to_owned()
-            ]
+use crate::archive;
+use crate::config::DatabaseSettings;
+use crate::db::{check_schema, connect, run_migrations, DBConnection};
+use crate::db_utils::{
+    delete_recipe, fetch_all_carts, fetch_all_recipes, fetch_one_cart_and_recipes,
+    fetch_one_recipe, fetch_recipes_with_tag, insert_cart, insert_into_cart, insert_recipe,
+    search_recipes,
+};
+use crate::exporters;
+use crate::importers::{self, jsonld};
+use crate::models::{Archive, ImportFormat, MergeStrategy, RecipeIn, RecipeWithIngredientsOut};
+use crate::seed;
+use crate::shopping_list;
+use crate::validation::validate_recipe;
+
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum RecipeFormat {
+    Text,
+    Markdown,
+    Json,
+    Jsonld,
+}
+
+fn exit_with_error(message: impl Display) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+#[must_use]
+pub fn open_connection(database_url: Option<String>) -> DBConnection {
+    let mut settings = match DatabaseSettings::from_figment(&rocket::Config::figment()) {
+        Ok(settings) => settings,
+        Err(error) => exit_with_error(format!("Invalid database configuration: {error}")),
+    };
+
+    if let Some(database_url) = database_url {
+        settings.url = database_url;
+    }
+
+    let mut connection = match connect(&settings).and_then(|pool| pool.get()) {
+        Ok(connection) => DBConnection(connection),
+        Err(error) => exit_with_error(format!(
+            "Error connecting to the database {}: {error}",
+            settings.url
+        )),
+    };
+
+    if let Err(error) = check_schema(&mut connection) {
+        exit_with_error(error);
+    }
+
+    connection
+}
+
+pub fn create_recipes(connection: &mut DBConnection, directory: &Path) {
+    let recipes = match seed::load_directory(directory) {
+        Ok(recipes) => recipes,
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{error}");
+            }
+
+            exit_with_error(format!(
+                "{} invalid seed entries, nothing inserted",
+                errors.len()
+            ));
         }
-    ];
+    };
 
     for recipe in recipes {
         match insert_recipe(&recipe, connection) {
@@ -63,10 +81,288 @@ pub fn create_recipes(connection: &mut DBConnection) {
             Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
                 println!("Recipe already exists: {}", recipe.name);
             }
-            Err(error) => {
-                eprintln!("Error inserting recipe: {error}");
-                std::process::exit(1);
+            Err(error) => exit_with_error(format!("Error inserting recipe: {error}")),
+        }
+    }
+}
+
+fn read_file(path: &str) -> String {
+    match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => exit_with_error(format!("Error reading {path}: {error}")),
+    }
+}
+
+fn detect_format(path: &Path) -> Option<ImportFormat> {
+    match path.extension().and_then(OsStr::to_str) {
+        Some("md" | "markdown" | "txt") => Some(ImportFormat::Markdown),
+        Some("cook") => Some(ImportFormat::Cooklang),
+        _ => None,
+    }
+}
+
+pub fn add_recipe(connection: &mut DBConnection, path: &str, format: Option<ImportFormat>) {
+    let content = read_file(path);
+    let file_path = Path::new(path);
+
+    let parsed = format.or_else(|| detect_format(file_path)).map_or_else(
+        || {
+            serde_json::from_str::<RecipeIn>(&content)
+                .or_else(|_| jsonld::parse(&content))
+                .map(|recipe_in| vec![recipe_in])
+        },
+        |format| {
+            importers::parse(
+                format,
+                &content,
+                file_path.file_stem().and_then(OsStr::to_str),
+            )
+        },
+    );
+
+    let recipes = match parsed {
+        Ok(recipes) => recipes,
+        Err(error) => exit_with_error(format!("Error parsing {path}: {error}")),
+    };
+
+    let mut failures = 0;
+
+    for recipe_in in &recipes {
+        if let Err(errors) = validate_recipe(recipe_in) {
+            eprintln!(
+                "Invalid recipe {}:\n  {}",
+                recipe_in.name,
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join("\n  ")
+            );
+            failures += 1;
+            continue;
+        }
+
+        match insert_recipe(recipe_in, connection) {
+            Ok(recipe) => println!("Inserted recipe {}: {}", recipe.id, recipe.name),
+            Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                eprintln!("Recipe already exists: {}", recipe_in.name);
+                failures += 1;
+            }
+            Err(error) => exit_with_error(format!("Error inserting recipe: {error}")),
+        }
+    }
+
+    if failures > 0 {
+        exit_with_error(format!(
+            "{failures} of {} recipes not inserted",
+            recipes.len()
+        ));
+    }
+}
+
+fn print_recipes(recipes: &[RecipeWithIngredientsOut]) {
+    for recipe in recipes {
+        if recipe.tags.is_empty() {
+            println!("{:>4}  {}", recipe.id, recipe.name);
+        } else {
+            println!(
+                "{:>4}  {} [{}]",
+                recipe.id,
+                recipe.name,
+                recipe.tags.join(", ")
+            );
+        }
+    }
+}
+
+pub fn list_recipes(connection: &mut DBConnection, tag: Option<&str>) {
+    let recipes = match tag {
+        Some(tag) => fetch_recipes_with_tag(tag, connection),
+        None => fetch_all_recipes(connection),
+    };
+
+    match recipes {
+        Ok(recipes) => print_recipes(&recipes),
+        Err(error) => exit_with_error(format!("Error listing recipes: {error}")),
+    }
+}
+
+pub fn search(connection: &mut DBConnection, query: &str) {
+    match search_recipes(query, connection) {
+        Ok(recipes) if recipes.is_empty() => println!("No recipe matches: {query}"),
+        Ok(recipes) => print_recipes(&recipes),
+        Err(error) => exit_with_error(format!("Error searching recipes: {error}")),
+    }
+}
+
+fn find_recipe(connection: &mut DBConnection, recipe_id: i32) -> RecipeWithIngredientsOut {
+    match fetch_one_recipe(recipe_id, connection) {
+        Ok(recipe) => recipe,
+        Err(DieselError::NotFound) => {
+            exit_with_error(format!("No recipe found with id {recipe_id}"))
+        }
+        Err(error) => exit_with_error(format!("Error fetching recipe: {error}")),
+    }
+}
+
+pub fn show_recipe(connection: &mut DBConnection, recipe_id: i32, format: RecipeFormat) {
+    let recipe = find_recipe(connection, recipe_id);
+
+    match format {
+        RecipeFormat::Text => print!("{}", exporters::plain_text::render(&recipe)),
+        RecipeFormat::Markdown => print!("{}", exporters::markdown::render(&recipe)),
+        RecipeFormat::Json => match serde_json::to_string_pretty(&recipe) {
+            Ok(json) => println!("{json}"),
+            Err(error) => exit_with_error(format!("Error serializing recipe: {error}")),
+        },
+        RecipeFormat::Jsonld => println!("{:#}", exporters::jsonld::render(&recipe)),
+    }
+}
+
+pub fn remove_recipe(connection: &mut DBConnection, recipe_id: i32) {
+    match delete_recipe(recipe_id, connection) {
+        Ok(0) => exit_with_error(format!("No recipe found with id {recipe_id}")),
+        Ok(_) => println!("Deleted recipe {recipe_id}"),
+        Err(error) => exit_with_error(format!("Error deleting recipe: {error}")),
+    }
+}
+
+pub fn create_cart(connection: &mut DBConnection) {
+    match insert_cart(connection) {
+        Ok(cart) => println!("Created cart {}", cart.id),
+        Err(error) => exit_with_error(format!("Error creating cart: {error}")),
+    }
+}
+
+pub fn add_to_cart(connection: &mut DBConnection, cart_id: i32, recipe_id: i32) {
+    let recipe = find_recipe(connection, recipe_id);
+
+    match insert_into_cart(cart_id, recipe_id, connection) {
+        Ok(_) => println!("Added {} to cart {cart_id}", recipe.name),
+        Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+            exit_with_error(format!("No cart found with id {cart_id}"));
+        }
+        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+            exit_with_error(format!(
+                "Recipe with id {recipe_id} is already in cart with id {cart_id}"
+            ));
+        }
+        Err(error) => exit_with_error(format!("Error adding recipe to cart: {error}")),
+    }
+}
+
+pub fn list_carts(connection: &mut DBConnection) {
+    let carts = match fetch_all_carts(connection) {
+        Ok(carts) => carts,
+        Err(error) => exit_with_error(format!("Error listing carts: {error}")),
+    };
+
+    for cart in carts {
+        let recipe_names: Vec<&str> = cart
+            .recipes
+            .iter()
+            .map(|recipe| recipe.name.as_str())
+            .collect();
+
+        println!(
+            "{:>4}  {}  {}",
+            cart.id,
+            cart.created_at,
+            recipe_names.join(", ")
+        );
+    }
+}
+
+pub fn print_shopping_list(connection: &mut DBConnection, cart_id: i32, pdf_path: Option<&str>) {
+    let cart = match fetch_one_cart_and_recipes(cart_id, connection) {
+        Ok(cart) => cart,
+        Err(DieselError::NotFound) => exit_with_error(format!("No cart found with id {cart_id}")),
+        Err(error) => exit_with_error(format!("Error fetching cart: {error}")),
+    };
+
+    if let Some(pdf_path) = pdf_path {
+        if let Err(error) = fs::write(pdf_path, exporters::pdf::render_shopping_list(&cart)) {
+            exit_with_error(format!("Error writing {pdf_path}: {error}"));
+        }
+
+        println!("Wrote shopping list to {pdf_path}");
+        return;
+    }
+
+    for aisle in shopping_list::build(&cart).aisles {
+        println!("{}", aisle.name);
+
+        for item in aisle.items {
+            println!("  [ ] {}", item.display);
+        }
+    }
+}
+
+pub fn export_archive(connection: &mut DBConnection, path: Option<&str>) {
+    let archive = match archive::export(connection) {
+        Ok(archive) => archive,
+        Err(error) => exit_with_error(format!("Error exporting archive: {error}")),
+    };
+
+    let Ok(body) = serde_json::to_string_pretty(&archive) else {
+        exit_with_error("Error serializing archive");
+    };
+
+    match path {
+        Some(path) => {
+            if let Err(error) = fs::write(path, body) {
+                exit_with_error(format!("Error writing {path}: {error}"));
+            }
+
+            println!(
+                "Exported {} recipes and {} carts to {path}",
+                archive.recipes.len(),
+                archive.carts.len()
+            );
+        }
+        None => println!("{body}"),
+    }
+}
+
+pub fn import_archive(connection: &mut DBConnection, path: &str, strategy: MergeStrategy) {
+    let archive = match serde_json::from_str::<Archive>(&read_file(path)) {
+        Ok(archive) => archive,
+        Err(error) => exit_with_error(format!("Error reading {path}: {error}")),
+    };
+
+    let report = match archive::import(&archive, strategy, connection) {
+        Ok(report) => report,
+        Err(error) => exit_with_error(format!("Error importing archive: {error}")),
+    };
+
+    for name in &report.created {
+        println!("Created recipe: {name}");
+    }
+
+    for name in &report.overwritten {
+        println!("Overwritten recipe: {name}");
+    }
+
+    for name in &report.skipped {
+        println!("Skipped existing recipe: {name}");
+    }
+
+    for renamed in &report.renamed {
+        println!("Renamed recipe: {} -> {}", renamed.from, renamed.to);
+    }
+
+    println!("Created {} carts", report.carts_created);
+    println!("Skipped {} carts", report.carts_skipped);
+}
+
+pub fn migrate(connection: &mut DBConnection) {
+    match run_migrations(connection) {
+        Ok(versions) if versions.is_empty() => println!("Database is up to date"),
+        Ok(versions) => {
+            for version in versions {
+                println!("Applied migration {version}");
             }
         }
+        Err(error) => exit_with_error(format!("Error running migrations: {error}")),
     }
 }
@@ -0,0 +1,219 @@
+use crate::models::RecipeIn;
+use crate::validation::validate_recipe;
+
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+pub const SEED_EXTENSION: &str = "toml";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidEntry {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for InvalidEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: {}", self.path.display(), self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct SeedFile {
+    #[serde(default)]
+    recipes: Vec<Spanned<RecipeIn>>,
+}
+
+fn line_number(content: &str, offset: usize) -> usize {
+    content
+        .bytes()
+        .take(offset)
+        .filter(|byte| *byte == b'\n')
+        .count()
+        + 1
+}
+
+pub fn parse(path: &Path, content: &str) -> Result<Vec<RecipeIn>, Vec<InvalidEntry>> {
+    let seed_file: SeedFile = match toml::from_str(content) {
+        Ok(seed_file) => seed_file,
+        Err(error) => {
+            return Err(vec![InvalidEntry {
+                path: path.to_owned(),
+                line: error.span().map(|span| line_number(content, span.start)),
+                message: error.message().to_owned(),
+            }])
+        }
+    };
+
+    let mut errors = Vec::new();
+
+    for recipe in &seed_file.recipes {
+        if let Err(recipe_errors) = validate_recipe(recipe.get_ref()) {
+            let line = line_number(content, recipe.span().start);
+
+            errors.extend(recipe_errors.into_iter().map(|error| InvalidEntry {
+                path: path.to_owned(),
+                line: Some(line),
+                message: error.message,
+            }));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(seed_file
+            .recipes
+            .into_iter()
+            .map(Spanned::into_inner)
+            .collect())
+    } else {
+        Err(errors)
+    }
+}
+
+fn seed_files(directory: &Path) -> Result<Vec<PathBuf>, InvalidEntry> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) => {
+            return Err(InvalidEntry {
+                path: directory.to_owned(),
+                line: None,
+                message: error.to_string(),
+            })
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == SEED_EXTENSION)
+        })
+        .collect();
+
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(InvalidEntry {
+            path: directory.to_owned(),
+            line: None,
+            message: format!("No .{SEED_EXTENSION} seed file found"),
+        });
+    }
+
+    Ok(paths)
+}
+
+pub fn load_directory(directory: &Path) -> Result<Vec<RecipeIn>, Vec<InvalidEntry>> {
+    let paths = match seed_files(directory) {
+        Ok(paths) => paths,
+        Err(error) => return Err(vec![error]),
+    };
+
+    let mut recipes = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in paths {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) => {
+                errors.push(InvalidEntry {
+                    path,
+                    line: None,
+                    message: error.to_string(),
+                });
+                continue;
+            }
+        };
+
+        match parse(&path, &content) {
+            Ok(parsed) => recipes.extend(parsed),
+            Err(parse_errors) => errors.extend(parse_errors),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(recipes)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const VALID_RECIPE: &str = r#"
+[[recipes]]
+name = "Tapenade"
+ingredients = ["200g d'olive noir"]
+steps = ["Mixer."]
+"#;
+
+    #[test]
+    fn parse_valid_file_test() {
+        let recipes = parse(Path::new("seeds/test.toml"), VALID_RECIPE).unwrap();
+
+        assert_eq!(recipes.len(), 1);
+        assert_eq!(recipes[0].name, "Tapenade");
+        assert_eq!(recipes[0].ingredients, vec!["200g d'olive noir"]);
+    }
+
+    #[rstest]
+    #[case(
+        "[[recipes]]\nname = \"Tapenade\"\nsteps = []\ningredients = [\nsteps = 3\n",
+        Some(5)
+    )]
+    #[case(
+        "[[recipes]]\nname = \"Tapenade\"\ningredients = []\nsteps = []\nprep_time_minutes = \"dix\"\n",
+        Some(5)
+    )]
+    fn parse_syntax_error_test(#[case] content: &str, #[case] expected_line: Option<usize>) {
+        let errors = parse(Path::new("seeds/test.toml"), content).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, expected_line);
+        assert!(errors[0]
+            .to_string()
+            .starts_with(&format!("seeds/test.toml:{}: ", expected_line.unwrap())));
+    }
+
+    #[test]
+    fn parse_invalid_recipe_test() {
+        let content = format!(
+            "{VALID_RECIPE}\n[[recipes]]\nname = \"Gratin\"\ningredients = []\nsteps = []\nprep_time_minutes = -5\n"
+        );
+
+        let errors = parse(Path::new("seeds/test.toml"), &content).unwrap_err();
+
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|error| error.line == Some(7)));
+    }
+
+    #[test]
+    fn load_shipped_seeds_test() {
+        let recipes = load_directory(Path::new("seeds")).unwrap();
+
+        assert_eq!(recipes.len(), 3);
+        assert_eq!(recipes[0].name, "Saucisses aux lentilles");
+    }
+
+    #[test]
+    fn load_missing_directory_test() {
+        let errors = load_directory(Path::new("seeds/missing")).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, None);
+    }
+}
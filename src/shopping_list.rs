@@ -0,0 +1,202 @@
+use crate::ingredient_formatter::format_parts;
+use crate::models::{AisleOut, CartWithRecipesOut, ShoppingListItemOut, ShoppingListOut};
+use crate::text::{fold, words};
+
+const OTHER_AISLE: &str = "Divers";
+
+const AISLES: [(&str, &[&str]); 6] = [
+    (
+        "Fruits et l\u{e9}gumes",
+        &[
+            "ail",
+            "aubergine",
+            "avocat",
+            "basilic",
+            "carotte",
+            "celeri",
+            "champignon",
+            "chou",
+            "ciboulette",
+            "citron",
+            "courgette",
+            "echalote",
+            "epinard",
+            "fraise",
+            "haricot",
+            "laitue",
+            "laurier",
+            "menthe",
+            "navet",
+            "oignon",
+            "orange",
+            "persil",
+            "poireau",
+            "poire",
+            "poivron",
+            "pomme",
+            "radis",
+            "salade",
+            "thym",
+            "tomate",
+        ],
+    ),
+    (
+        "Boucherie et poissonnerie",
+        &[
+            "agneau",
+            "anchoi",
+            "boeuf",
+            "cabillaud",
+            "canard",
+            "crevette",
+            "dinde",
+            "jambon",
+            "lardon",
+            "merguez",
+            "porc",
+            "poulet",
+            "saucisse",
+            "saumon",
+            "thon",
+            "veau",
+            "viande",
+        ],
+    ),
+    (
+        "Cr\u{e8}merie",
+        &[
+            "beurre",
+            "comte",
+            "creme",
+            "emmental",
+            "feta",
+            "fromage",
+            "gruyere",
+            "lait",
+            "mascarpone",
+            "mozzarella",
+            "oeuf",
+            "parmesan",
+            "ricotta",
+            "yaourt",
+        ],
+    ),
+    (
+        "\u{c9}picerie",
+        &[
+            "bouillon",
+            "cacao",
+            "capre",
+            "chocolat",
+            "cornichon",
+            "cumin",
+            "farine",
+            "huile",
+            "lentille",
+            "levure",
+            "miel",
+            "moutarde",
+            "muscade",
+            "olive",
+            "pate",
+            "pignon",
+            "poivre",
+            "riz",
+            "sel",
+            "semoule",
+            "sucre",
+            "vanille",
+            "vinaigre",
+        ],
+    ),
+    ("Boulangerie", &["baguette", "brioche", "pain"]),
+    ("Boissons", &["biere", "cidre", "jus", "vin"]),
+];
+
+#[must_use]
+pub fn aisle(ingredient_name: &str) -> &'static str {
+    let Some(head) = words(ingredient_name).into_iter().next() else {
+        return OTHER_AISLE;
+    };
+
+    AISLES
+        .iter()
+        .find(|(_, keywords)| keywords.contains(&head.as_str()))
+        .map_or(OTHER_AISLE, |(name, _)| name)
+}
+
+#[must_use]
+pub fn build(cart: &CartWithRecipesOut) -> ShoppingListOut {
+    let mut items: Vec<(Vec<String>, String, ShoppingListItemOut)> = Vec::new();
+
+    for ingredient in cart.recipes.iter().flat_map(|recipe| &recipe.ingredients) {
+        let key = words(&ingredient.name);
+        let unit = fold(&ingredient.unit);
+
+        if let Some((_, _, item)) = items
+            .iter_mut()
+            .find(|(item_key, item_unit, _)| *item_key == key && *item_unit == unit)
+        {
+            item.quantity += ingredient.quantity;
+            item.display = format_parts(&item.name, item.quantity, &item.unit);
+        } else {
+            items.push((
+                key,
+                unit,
+                ShoppingListItemOut {
+                    name: ingredient.name.clone(),
+                    quantity: ingredient.quantity,
+                    unit: ingredient.unit.clone(),
+                    display: ingredient.display.clone(),
+                },
+            ));
+        }
+    }
+
+    items.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+    let mut aisles: Vec<AisleOut> = AISLES
+        .iter()
+        .map(|(name, _)| *name)
+        .chain([OTHER_AISLE])
+        .map(|name| AisleOut {
+            name: name.to_owned(),
+            items: Vec::new(),
+        })
+        .collect();
+
+    for (_, _, item) in items {
+        let aisle_name = match aisle(&item.name) {
+            OTHER_AISLE => aisle(&item.unit),
+            aisle_name => aisle_name,
+        };
+
+        if let Some(aisle) = aisles.iter_mut().find(|aisle| aisle.name == aisle_name) {
+            aisle.items.push(item);
+        }
+    }
+
+    aisles.retain(|aisle| !aisle.items.is_empty());
+
+    ShoppingListOut {
+        cart_id: cart.id,
+        aisles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Oignons rouges", "Fruits et l\u{e9}gumes")]
+    #[case("saucisses de Montb\u{e9}liard", "Boucherie et poissonnerie")]
+    #[case("\u{c9}pinards surgel\u{e9}s", "Fruits et l\u{e9}gumes")]
+    #[case("cr\u{e8}me fra\u{ee}che", "Cr\u{e8}merie")]
+    #[case("huile d'olive", "\u{c9}picerie")]
+    #[case("papier cuisson", "Divers")]
+    fn aisle_test(#[case] test_input: &str, #[case] expected_aisle: &str) {
+        assert_eq!(aisle(test_input), expected_aisle);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::models::IngredientOut;
+use crate::text::words;
+
+use regex::Regex;
+
+const PASSIVE_KEYWORDS: [&str; 14] = [
+    "cuire",
+    "cuisson",
+    "enfourner",
+    "four",
+    "mijoter",
+    "fr\u{e9}mir",
+    "reposer",
+    "repos",
+    "mariner",
+    "refroidir",
+    "r\u{e9}frig\u{e9}rateur",
+    "frigo",
+    "lever",
+    "laisser",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StepTiming {
+    pub duration_minutes: Option<i32>,
+    pub temperature_celsius: Option<i32>,
+    pub passive: bool,
+}
+
+fn parse_number(number: Option<regex::Match>) -> i32 {
+    number.map_or(0, |res| res.as_str().parse::<i32>().unwrap_or(0))
+}
+
+fn parse_duration(description: &str) -> Option<i32> {
+    let Ok(re) = Regex::new(
+        r"(?i)\b(\d+)\s*(?:heures?|h)(?:\s*(\d+)(?:\s*(?:minutes?|min|mn))?)?\b|\b(\d+)\s*(?:minutes?|min|mn)\b|\b(\d+)\s*(?:secondes?|sec)\b",
+    ) else {
+        return None;
+    };
+
+    let durations: Vec<i32> = re
+        .captures_iter(description)
+        .filter_map(|caps| {
+            if caps.get(1).is_some() {
+                parse_number(caps.get(1))
+                    .checked_mul(60)
+                    .and_then(|minutes| minutes.checked_add(parse_number(caps.get(2))))
+            } else if caps.get(3).is_some() {
+                Some(parse_number(caps.get(3)))
+            } else {
+                parse_number(caps.get(4))
+                    .checked_add(59)
+                    .map(|seconds| seconds / 60)
+            }
+        })
+        .collect();
+
+    if durations.is_empty() {
+        None
+    } else {
+        durations
+            .iter()
+            .try_fold(0_i32, |total, duration| total.checked_add(*duration))
+    }
+}
+
+fn parse_temperature(description: &str) -> Option<i32> {
+    let Ok(re) = Regex::new(r"(?i)\b(\d+)\s*\x{b0}\s*C?|\b(?:thermostat|th\.?)\s*(\d+)\b") else {
+        return None;
+    };
+
+    let caps = re.captures(description)?;
+
+    if caps.get(1).is_some() {
+        Some(parse_number(caps.get(1)))
+    } else {
+        parse_number(caps.get(2)).checked_mul(30)
+    }
+}
+
+fn is_passive(description: &str) -> bool {
+    let description = description.to_lowercase();
+
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| PASSIVE_KEYWORDS.contains(&word))
+}
+
+#[must_use]
+pub fn parse(description: &str) -> StepTiming {
+    let duration_minutes = parse_duration(description);
+
+    StepTiming {
+        duration_minutes,
+        temperature_celsius: parse_temperature(description),
+        passive: duration_minutes.is_some() && is_passive(description),
+    }
+}
+
+#[must_use]
+pub fn mentioned_ingredients(description: &str, ingredients: &[IngredientOut]) -> Vec<i32> {
+    let description_words = words(description);
+
+    ingredients
+        .iter()
+        .filter(|ingredient| {
+            words(&ingredient.name)
+                .first()
+                .is_some_and(|head| description_words.contains(head))
+        })
+        .map(|ingredient| ingredient.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Faire cuire pendant 40 minutes sur feu moyen \u{e0} couvert.", (Some(40), None, true))]
+    #[case("Enfourner pour 20 minutes \u{e0} 180\u{b0}C. Servir aussit\u{f4}t.", (Some(20), Some(180), true))]
+    #[case("Laisser reposer 1h30 au frais.", (Some(90), None, true))]
+    #[case("Laisser lever 2 heures.", (Some(120), None, true))]
+    #[case("Laisser mariner 1 h 15 min.", (Some(75), None, true))]
+    #[case("P\u{e9}trir la p\u{e2}te 10 min.", (Some(10), None, false))]
+    #[case("Fouetter 30 secondes.", (Some(1), None, false))]
+    #[case("Pr\u{e9}chauffer le four \u{e0} 180 \u{b0}C.", (None, Some(180), false))]
+    #[case("Cuire au four thermostat 6.", (None, Some(180), false))]
+    #[case("M\u{e9}langer 5 minutes puis cuire 10 minutes.", (Some(15), None, true))]
+    #[case("Ajouter 70 cl d'eau, saler et poivrer.", (None, None, false))]
+    #[case("Laisser reposer 99999999 heures.", (None, None, false))]
+    #[case("Laisser reposer 2000000000 minutes puis 2000000000 minutes.", (None, None, false))]
+    #[case("Laisser reposer 99999999 heures puis 10 minutes.", (Some(10), None, true))]
+    #[case("Cuire au four thermostat 99999999.", (None, None, false))]
+    fn step_parsing_test(
+        #[case] test_input: &str,
+        #[case] expected_timing: (Option<i32>, Option<i32>, bool),
+    ) {
+        assert_eq!(
+            parse(test_input),
+            StepTiming {
+                duration_minutes: expected_timing.0,
+                temperature_celsius: expected_timing.1,
+                passive: expected_timing.2,
+            }
+        );
+    }
+
+    #[test]
+    fn mentioned_ingredients_test() {
+        let ingredients: Vec<IngredientOut> = [
+            "Lentilles vertes",
+            "saucisses de Montb\u{e9}liard",
+            "lardons fum\u{e9}s",
+            "oignon",
+            "ail",
+            "laurier",
+        ]
+        .into_iter()
+        .zip(1..)
+        .map(|(name, id)| IngredientOut {
+            id,
+            preposition: String::new(),
+            name: name.to_owned(),
+            quantity: 1.0,
+            unit: String::new(),
+            display: format!("1 {name}"),
+        })
+        .collect();
+
+        assert_eq!(
+            mentioned_ingredients(
+                "Eplucher et \u{e9}mincer l'oignon. Peler les gousses d'ail.",
+                &ingredients
+            ),
+            vec![4, 5]
+        );
+        assert_eq!(
+            mentioned_ingredients(
+                "Dans une cocotte, mettre les lentilles, les saucisses, les lardons, l'oignon \u{e9}minces, les gousses d'ail et les feuilles de laurier.",
+                &ingredients
+            ),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            mentioned_ingredients("Servir bien chaud.", &ingredients),
+            Vec::<i32>::new()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+#[must_use]
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+
+    for c in text.to_lowercase().chars() {
+        match c {
+            '\u{e0}'..='\u{e5}' => folded.push('a'),
+            '\u{e7}' => folded.push('c'),
+            '\u{e8}'..='\u{eb}' => folded.push('e'),
+            '\u{ec}'..='\u{ef}' => folded.push('i'),
+            '\u{f1}' => folded.push('n'),
+            '\u{f2}'..='\u{f6}' => folded.push('o'),
+            '\u{f9}'..='\u{fc}' => folded.push('u'),
+            '\u{fd}' | '\u{ff}' => folded.push('y'),
+            '\u{e6}' => folded.push_str("ae"),
+            '\u{153}' => folded.push_str("oe"),
+            _ => folded.push(c),
+        }
+    }
+
+    folded
+}
+
+#[must_use]
+pub fn singular(word: &str) -> &str {
+    match word.strip_suffix(['s', 'x']) {
+        Some(stem) if stem.chars().count() > 2 => stem,
+        _ => word,
+    }
+}
+
+#[must_use]
+pub fn words(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| singular(word).to_owned())
+        .collect()
+}
+
+#[must_use]
+pub fn slug(text: &str) -> String {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+#[must_use]
+pub fn normalize_name(name: &str) -> String {
+    fold(name)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("\u{c9}pinards surgel\u{e9}s", vec!["epinard", "surgele"])]
+    #[case("les gousses d'ail", vec!["les", "gousse", "d", "ail"])]
+    #[case("l\u{2019}huile d\u{2019}olive", vec!["l", "huile", "d", "olive"])]
+    #[case("Gnocchi", vec!["gnocchi"])]
+    #[case("C\u{153}ur de b\u{153}uf", vec!["coeur", "de", "boeuf"])]
+    fn words_test(#[case] test_input: &str, #[case] expected_words: Vec<&str>) {
+        assert_eq!(words(test_input), expected_words);
+    }
+
+    #[rstest]
+    #[case("V\u{e9}g\u{e9}tarien", "vegetarien")]
+    #[case("  Plat   principal ", "plat-principal")]
+    #[case("Cuisine d'\u{e9}t\u{e9}", "cuisine-d-ete")]
+    fn slug_test(#[case] test_input: &str, #[case] expected_slug: &str) {
+        assert_eq!(slug(test_input), expected_slug);
+    }
+
+    #[rstest]
+    #[case("Tapenade", "tapenade")]
+    #[case(" tapenade  ", "tapenade")]
+    #[case("P\u{e2}tes  \u{e0} la Carbonara", "pates a la carbonara")]
+    #[case("Cuisine d'\u{e9}t\u{e9}", "cuisine d'ete")]
+    fn normalize_name_test(#[case] test_input: &str, #[case] expected_name: &str) {
+        assert_eq!(normalize_name(test_input), expected_name);
+    }
+}
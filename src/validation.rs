@@ -0,0 +1,326 @@
+use crate::ingredient_parser;
+use crate::models::RecipeIn;
+use crate::response::ErrorDetail;
+use crate::text::{fold, slug};
+
+use rocket::http::uri::Absolute;
+
+pub const DIFFICULTIES: [&str; 4] = ["tr\u{e8}s facile", "facile", "moyenne", "difficile"];
+pub const NAME_MAX_LENGTH: usize = 120;
+
+fn validate_time(field: &str, minutes: Option<i32>, errors: &mut Vec<ErrorDetail>) {
+    if minutes.is_some_and(|minutes| minutes < 0) {
+        errors.push(
+            ErrorDetail::new(
+                "negative_time",
+                format!("{field} must be a non-negative number of minutes"),
+            )
+            .at(format!("/{field}")),
+        );
+    }
+}
+
+fn is_valid_url(url: &str) -> bool {
+    Absolute::parse(url).is_ok_and(|uri| {
+        matches!(uri.scheme(), "http" | "https")
+            && uri
+                .authority()
+                .is_some_and(|authority| !authority.host().is_empty())
+    })
+}
+
+#[must_use]
+pub fn validate_name(name: &str) -> Option<ErrorDetail> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        Some(
+            ErrorDetail::new("blank_name", "name must not be blank".to_owned())
+                .at("/name".to_owned()),
+        )
+    } else if name.chars().count() > NAME_MAX_LENGTH {
+        Some(
+            ErrorDetail::new(
+                "name_too_long",
+                format!("name must be at most {NAME_MAX_LENGTH} characters"),
+            )
+            .at("/name".to_owned()),
+        )
+    } else {
+        None
+    }
+}
+
+fn check_ingredients<'a>(
+    ingredients: impl ExactSizeIterator<Item = &'a str>,
+    parse: bool,
+    errors: &mut Vec<ErrorDetail>,
+) {
+    if ingredients.len() == 0 {
+        errors.push(
+            ErrorDetail::new(
+                "missing_ingredients",
+                "ingredients must contain at least one ingredient".to_owned(),
+            )
+            .at("/ingredients".to_owned()),
+        );
+    }
+
+    let mut seen: Vec<String> = Vec::with_capacity(ingredients.len());
+
+    for (index, raw_ingredient) in ingredients.enumerate() {
+        if raw_ingredient.trim().is_empty() {
+            errors.push(
+                ErrorDetail::new(
+                    "blank_ingredient",
+                    format!("ingredients[{index}] must not be blank"),
+                )
+                .at(format!("/ingredients/{index}")),
+            );
+            continue;
+        }
+
+        let folded = fold(raw_ingredient.trim());
+
+        if seen.contains(&folded) {
+            errors.push(
+                ErrorDetail::new(
+                    "duplicate_ingredient",
+                    format!("ingredients[{index}] is duplicated: {raw_ingredient}"),
+                )
+                .at(format!("/ingredients/{index}")),
+            );
+        } else if parse && ingredient_parser::parse(raw_ingredient).is_err() {
+            errors.push(
+                ErrorDetail::new(
+                    "invalid_ingredient",
+                    format!(
+                        "ingredients[{index}] is not in the '<quantity> [unit] [de] <name>' format: {raw_ingredient}"
+                    ),
+                )
+                .at(format!("/ingredients/{index}")),
+            );
+        }
+
+        seen.push(folded);
+    }
+}
+
+#[must_use]
+pub fn validate_ingredient_names<'a>(
+    names: impl ExactSizeIterator<Item = &'a str>,
+) -> Vec<ErrorDetail> {
+    let mut errors = Vec::new();
+
+    check_ingredients(names, false, &mut errors);
+
+    errors
+}
+
+#[must_use]
+pub fn validate_step(field: &str, source: String, description: &str) -> Option<ErrorDetail> {
+    if description.trim().is_empty() {
+        Some(ErrorDetail::new("blank_step", format!("{field} must not be blank")).at(source))
+    } else {
+        None
+    }
+}
+
+#[must_use]
+pub fn validate_tag(field: &str, source: String, tag: &str) -> Option<ErrorDetail> {
+    if tag.trim().is_empty() {
+        Some(ErrorDetail::new("blank_tag", format!("{field} must not be blank")).at(source))
+    } else if slug(tag).is_empty() {
+        Some(
+            ErrorDetail::new(
+                "invalid_tag",
+                format!("{field} must contain a letter or a digit: {}", tag.trim()),
+            )
+            .at(source),
+        )
+    } else {
+        None
+    }
+}
+
+#[must_use]
+pub fn validate_metadata(recipe_in: &RecipeIn) -> Vec<ErrorDetail> {
+    let mut errors = Vec::new();
+
+    validate_time(
+        "prep_time_minutes",
+        recipe_in.prep_time_minutes,
+        &mut errors,
+    );
+    validate_time(
+        "cook_time_minutes",
+        recipe_in.cook_time_minutes,
+        &mut errors,
+    );
+
+    if let Some(difficulty) = &recipe_in.difficulty {
+        if !DIFFICULTIES.contains(&difficulty.as_str()) {
+            errors.push(
+                ErrorDetail::new(
+                    "unknown_difficulty",
+                    format!("difficulty must be one of: {}", DIFFICULTIES.join(", ")),
+                )
+                .at("/difficulty".to_owned()),
+            );
+        }
+    }
+
+    if let Some(source_url) = &recipe_in.source_url {
+        if !is_valid_url(source_url) {
+            errors.push(
+                ErrorDetail::new(
+                    "invalid_url",
+                    format!("source_url is not a valid http(s) URL: {source_url}"),
+                )
+                .at("/source_url".to_owned()),
+            );
+        }
+    }
+
+    errors.extend(
+        recipe_in
+            .tags
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tag)| validate_tag("tags", format!("/tags/{index}"), tag)),
+    );
+
+    errors
+}
+
+pub fn validate_recipe(recipe_in: &RecipeIn) -> Result<(), Vec<ErrorDetail>> {
+    let mut errors: Vec<ErrorDetail> = validate_name(&recipe_in.name).into_iter().collect();
+
+    check_ingredients(
+        recipe_in.ingredients.iter().map(String::as_str),
+        true,
+        &mut errors,
+    );
+
+    errors.extend(
+        recipe_in
+            .steps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, step)| {
+                validate_step(&format!("steps[{index}]"), format!("/steps/{index}"), step)
+            }),
+    );
+
+    errors.extend(validate_metadata(recipe_in));
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("https://www.marmiton.org/recettes/recette_tapenade_11219.aspx", true)]
+    #[case("http://localhost:8000/recette", true)]
+    #[case("ftp://example.com/recette", false)]
+    #[case("www.marmiton.org", false)]
+    #[case("https://", false)]
+    #[case("pas une url", false)]
+    fn url_validation_test(#[case] test_input: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_url(test_input), expected);
+    }
+
+    #[rstest]
+    #[case("Tapenade", None)]
+    #[case("  Tapenade  ", None)]
+    #[case(" ", Some("blank_name"))]
+    #[case(&"a".repeat(NAME_MAX_LENGTH), None)]
+    #[case(&"\u{e9}".repeat(NAME_MAX_LENGTH + 1), Some("name_too_long"))]
+    fn name_validation_test(#[case] name: &str, #[case] expected: Option<&str>) {
+        assert_eq!(validate_name(name).map(|error| error.code), expected);
+    }
+
+    #[test]
+    fn ingredients_validation_test() {
+        let recipe_in = RecipeIn {
+            name: "Salade".to_owned(),
+            ingredients: vec![
+                "1 salade".to_owned(),
+                " ".to_owned(),
+                "1 Salade ".to_owned(),
+            ],
+            steps: vec!["Laver.".to_owned(), String::new()],
+            ..Default::default()
+        };
+
+        let errors = validate_recipe(&recipe_in).unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.code, error.source.as_deref()))
+                .collect::<Vec<(&str, Option<&str>)>>(),
+            vec![
+                ("blank_ingredient", Some("/ingredients/1")),
+                ("duplicate_ingredient", Some("/ingredients/2")),
+                ("blank_step", Some("/steps/1")),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("Laver.", None)]
+    #[case("  ", Some(("blank_step", "description must not be blank")))]
+    fn step_validation_test(#[case] description: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(
+            validate_step("description", "/description".to_owned(), description)
+                .as_ref()
+                .map(|error| (error.code, error.message.as_str())),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("V\u{e9}g\u{e9}tarien", None)]
+    #[case("  ", Some(("blank_tag", "tags must not be blank")))]
+    #[case(
+        " ??? ",
+        Some(("invalid_tag", "tags must contain a letter or a digit: ???"))
+    )]
+    fn tag_validation_test(#[case] tag: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(
+            validate_tag("tags", "/tags/0".to_owned(), tag)
+                .as_ref()
+                .map(|error| (error.code, error.message.as_str())),
+            expected
+        );
+    }
+
+    #[test]
+    fn ingredient_names_validation_test() {
+        assert_eq!(
+            validate_ingredient_names(["sel", "", "Sel "].into_iter())
+                .iter()
+                .map(|error| (error.code, error.source.as_deref()))
+                .collect::<Vec<(&str, Option<&str>)>>(),
+            vec![
+                ("blank_ingredient", Some("/ingredients/1")),
+                ("duplicate_ingredient", Some("/ingredients/2")),
+            ]
+        );
+        assert_eq!(
+            validate_ingredient_names(std::iter::empty())
+                .iter()
+                .map(|error| error.code)
+                .collect::<Vec<&str>>(),
+            vec!["missing_ingredients"]
+        );
+    }
+}
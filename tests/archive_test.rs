@@ -0,0 +1,381 @@
+use cooking_book::models::{
+    CartWithRecipesOut, Data, ImportReportOut, RecipeWithIngredientsOut, RenamedRecipeOut,
+};
+use cooking_book::response::{Errors, HTTPError};
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rstest::{fixture, rstest};
+use serde_json::{json, Value};
+
+mod common;
+use common::{client, http_error};
+
+fn retrieve_recipes(client: &Client) -> Vec<RecipeWithIngredientsOut> {
+    client
+        .get("/api/recipes")
+        .dispatch()
+        .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+        .unwrap()
+        .data
+}
+
+fn retrieve_cart(cart_id: i32, client: &Client) -> CartWithRecipesOut {
+    client
+        .get(format!("/api/carts/{cart_id}"))
+        .dispatch()
+        .into_json::<Data<CartWithRecipesOut>>()
+        .unwrap()
+        .data
+}
+
+#[fixture]
+fn create_recipe_in_cart(client: Client) -> Client {
+    let create_recipe_response_status = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Recette",
+                "ingredients": ["20 g de sucre", "2 oeufs"],
+                "steps": ["Battre les oeufs avec le sucre.", "Laisser reposer 30 minutes au frais."],
+                "description": "Une recette",
+                "prep_time_minutes": 5,
+                "difficulty": "facile",
+                "tags": ["dessert"]
+            }
+        ))
+        .dispatch()
+        .status();
+
+    assert_eq!(create_recipe_response_status, Status::Created);
+
+    client.post("/api/carts").dispatch();
+    client.post("/api/carts/1/recipes/1").dispatch();
+
+    client
+}
+
+#[rstest]
+fn export_import_round_trip_test(create_recipe_in_cart: Client) {
+    let source_client = create_recipe_in_cart;
+
+    let export_response = source_client.get("/api/export").dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::JSON));
+
+    let archive = export_response.into_json::<Value>().unwrap();
+
+    assert_eq!(archive["version"], json!(1));
+    assert_eq!(archive["recipes"][0]["name"], json!("Recette"));
+    assert_eq!(
+        archive["recipes"][0]["ingredients"][1],
+        json!({"preposition": "", "name": "oeufs", "quantity": 2.0, "unit": ""})
+    );
+    assert_eq!(
+        archive["recipes"][0]["steps"][0]["ingredients"],
+        json!([0, 1])
+    );
+    assert_eq!(archive["carts"][0]["recipes"], json!(["Recette"]));
+
+    let other_client = client::default();
+
+    let import_response = other_client
+        .post("/api/import")
+        .body(archive.to_string())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Ok);
+    assert_eq!(
+        import_response
+            .into_json::<Data<ImportReportOut>>()
+            .unwrap()
+            .data,
+        ImportReportOut {
+            created: vec!["Recette".to_string()],
+            carts_created: 1,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        retrieve_recipes(&other_client),
+        retrieve_recipes(&source_client)
+    );
+    assert_eq!(
+        retrieve_cart(1, &other_client),
+        retrieve_cart(1, &source_client)
+    );
+}
+
+fn conflicting_archive() -> Value {
+    json!({
+        "version": 1,
+        "exported_at": "2024-10-12T10:00:00Z",
+        "recipes": [
+            {
+                "name": "Recette",
+                "description": null,
+                "prep_time_minutes": null,
+                "cook_time_minutes": null,
+                "difficulty": null,
+                "source_url": null,
+                "notes": null,
+                "ingredients": [{"preposition": "de ", "name": "sel", "quantity": 30.0, "unit": "g"}],
+                "steps": [{"description": "Saler.", "duration_minutes": null, "temperature_celsius": null, "passive": false, "ingredients": [0]}]
+            }
+        ],
+        "carts": [{"created_at": "2024-10-12T09:00:00Z", "recipes": ["Recette"]}]
+    })
+}
+
+#[rstest]
+#[case(
+    "skip",
+    ImportReportOut { skipped: vec!["Recette".to_string()], carts_created: 1, ..Default::default() },
+    vec![("Recette", "20 g de sucre")],
+)]
+#[case(
+    "overwrite",
+    ImportReportOut { overwritten: vec!["Recette".to_string()], carts_created: 1, ..Default::default() },
+    vec![("Recette", "30 g de sel")],
+)]
+#[case(
+    "rename",
+    ImportReportOut {
+        renamed: vec![RenamedRecipeOut { from: "Recette".to_string(), to: "Recette (2)".to_string() }],
+        carts_created: 1,
+        ..Default::default()
+    },
+    vec![("Recette", "20 g de sucre"), ("Recette (2)", "30 g de sel")],
+)]
+fn import_merge_strategy_test(
+    create_recipe_in_cart: Client,
+    #[case] strategy: &str,
+    #[case] expected_report: ImportReportOut,
+    #[case] expected_recipes: Vec<(&str, &str)>,
+) {
+    let client = create_recipe_in_cart;
+
+    let import_response = client
+        .post(format!("/api/import?strategy={strategy}"))
+        .body(conflicting_archive().to_string())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Ok);
+    assert_eq!(
+        import_response
+            .into_json::<Data<ImportReportOut>>()
+            .unwrap()
+            .data,
+        expected_report
+    );
+
+    let recipes = retrieve_recipes(&client);
+
+    assert_eq!(
+        recipes
+            .iter()
+            .map(|recipe| (recipe.name.as_str(), recipe.ingredients[0].display.as_str()))
+            .collect::<Vec<(&str, &str)>>(),
+        expected_recipes
+    );
+
+    let imported_cart = retrieve_cart(2, &client);
+
+    assert_eq!(imported_cart.created_at, "2024-10-12T09:00:00Z");
+    assert_eq!(
+        imported_cart.recipes[0].name,
+        expected_recipes.last().unwrap().0
+    );
+    assert!(imported_cart.recipes[0].steps[0]
+        .ingredient_ids
+        .contains(&imported_cart.recipes[0].ingredients[0].id));
+}
+
+#[rstest]
+fn import_trims_recipe_names_test(client: Client) {
+    let mut archive = conflicting_archive();
+    archive["recipes"][0]["name"] = json!("  Recette ");
+    archive["carts"][0]["recipes"][0] = json!("  Recette ");
+
+    let import_response = client
+        .post("/api/import")
+        .body(archive.to_string())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Ok);
+    assert_eq!(retrieve_recipes(&client)[0].name, "Recette");
+    assert_eq!(retrieve_cart(1, &client).recipes[0].name, "Recette");
+}
+
+#[rstest]
+#[case(
+    "skip",
+    ImportReportOut { skipped: vec!["Recette".to_string()], carts_skipped: 1, ..Default::default() },
+)]
+#[case(
+    "overwrite",
+    ImportReportOut { overwritten: vec!["Recette".to_string()], carts_skipped: 1, ..Default::default() },
+)]
+fn import_same_archive_twice_test(
+    create_recipe_in_cart: Client,
+    #[case] strategy: &str,
+    #[case] expected_report: ImportReportOut,
+) {
+    let client = create_recipe_in_cart;
+
+    let reports: Vec<ImportReportOut> = (0..2)
+        .map(|_| {
+            let import_response = client
+                .post(format!("/api/import?strategy={strategy}"))
+                .body(conflicting_archive().to_string())
+                .dispatch();
+
+            assert_eq!(import_response.status(), Status::Ok);
+
+            import_response
+                .into_json::<Data<ImportReportOut>>()
+                .unwrap()
+                .data
+        })
+        .collect();
+
+    assert_eq!(reports[0].carts_created, 1);
+    assert_eq!(reports[1], expected_report);
+    assert_eq!(
+        client.get("/api/carts/3").dispatch().status(),
+        Status::NotFound
+    );
+}
+
+#[rstest]
+#[case(
+    json!({"version": 2, "exported_at": "2024-10-12T10:00:00Z"}),
+    vec![(
+        "unsupported_archive_version",
+        "/version",
+        "Unsupported archive version 2, expected 1"
+    )],
+)]
+#[case(
+    json!({
+        "version": 1,
+        "exported_at": "2024-10-12T10:00:00Z",
+        "recipes": [
+            {
+                "name": "Recette",
+                "description": null,
+                "prep_time_minutes": -5,
+                "cook_time_minutes": null,
+                "difficulty": null,
+                "source_url": null,
+                "notes": null,
+                "steps": [{"description": "Saler.", "duration_minutes": null, "temperature_celsius": null, "passive": false, "ingredients": [3]}]
+            }
+        ],
+        "carts": [{"created_at": "hier", "recipes": ["Autre recette"]}]
+    }),
+    vec![
+        (
+            "missing_ingredients",
+            "/recipes/0/ingredients",
+            "recipes[0].ingredients must contain at least one ingredient"
+        ),
+        (
+            "negative_time",
+            "/recipes/0/prep_time_minutes",
+            "recipes[0].prep_time_minutes must be a non-negative number of minutes"
+        ),
+        (
+            "unknown_ingredient",
+            "/recipes/0/steps/0/ingredients/0",
+            "recipes[0].steps[0] references unknown ingredient 3"
+        ),
+        (
+            "invalid_date",
+            "/carts/0/created_at",
+            "carts[0].created_at is not an RFC 3339 date: hier"
+        ),
+        (
+            "unknown_recipe",
+            "/carts/0/recipes/0",
+            "carts[0] references unknown recipe: Autre recette"
+        ),
+    ],
+)]
+#[case(
+    json!({
+        "version": 1,
+        "exported_at": "2024-10-12T10:00:00Z",
+        "recipes": [
+            {
+                "name": "Recette",
+                "description": null,
+                "prep_time_minutes": null,
+                "cook_time_minutes": null,
+                "difficulty": null,
+                "source_url": null,
+                "notes": null,
+                "ingredients": [
+                    {"preposition": "de ", "name": "sel", "quantity": 30.0, "unit": "g"},
+                    {"preposition": "", "name": " ", "quantity": 1.0, "unit": ""},
+                    {"preposition": "de ", "name": "Sel", "quantity": 5.0, "unit": "g"}
+                ],
+                "steps": [{"description": " ", "duration_minutes": null, "temperature_celsius": null, "passive": false, "ingredients": []}]
+            }
+        ],
+        "carts": []
+    }),
+    vec![
+        (
+            "blank_ingredient",
+            "/recipes/0/ingredients/1",
+            "recipes[0].ingredients[1] must not be blank"
+        ),
+        (
+            "duplicate_ingredient",
+            "/recipes/0/ingredients/2",
+            "recipes[0].ingredients[2] is duplicated: Sel"
+        ),
+        (
+            "blank_step",
+            "/recipes/0/steps/0/description",
+            "recipes[0].steps[0].description must not be blank"
+        ),
+    ],
+)]
+fn import_invalid_archive_test(
+    client: Client,
+    #[case] archive: Value,
+    #[case] expected_errors: Vec<(&str, &str, &str)>,
+) {
+    let import_response = client
+        .post("/api/import")
+        .body(archive.to_string())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        import_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: expected_errors
+                .into_iter()
+                .map(|(code, source, message)| HTTPError {
+                    source: Some(source.to_string()),
+                    ..http_error(Status::UnprocessableEntity, code, message)
+                })
+                .collect()
+        }
+    );
+    assert!(retrieve_recipes(&client).is_empty());
+}
+
+#[rstest]
+fn import_malformed_archive_test(client: Client) {
+    let import_response = client.post("/api/import").body("{\"recipes\": ").dispatch();
+
+    assert_eq!(import_response.status(), Status::UnprocessableEntity);
+    assert!(import_response.into_json::<Errors>().unwrap().errors[0]
+        .message
+        .starts_with("Invalid archive: "));
+}
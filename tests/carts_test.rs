@@ -1,5 +1,5 @@
 use cooking_book::models::{CartWithRecipesOut, Data, RecipeWithIngredientsOut};
-use cooking_book::response::{Errors, HTTPError};
+use cooking_book::response::Errors;
 
 use rocket::http::Status;
 use rocket::local::blocking::Client;
@@ -9,7 +9,7 @@ use time::format_description::well_known::Rfc3339;
 use time::{Duration, OffsetDateTime};
 
 mod common;
-use common::client;
+use common::{client, http_error};
 
 #[rstest]
 fn create_retrieve_delete_cart_test(client: Client) {
@@ -71,7 +71,7 @@ fn add_recipe_to_cart_test(client: Client) {
 
     let create_recipe_response = client
         .post("/api/recipes")
-        .json(&json!({"name": "Recette 1", "ingredients": [], "steps": []}))
+        .json(&json!({"name": "Recette 1", "ingredients": ["1 citron"], "steps": []}))
         .dispatch();
 
     assert_eq!(create_recipe_response.status(), Status::Created);
@@ -113,7 +113,7 @@ fn create_cart_and_recipe(client: Client) -> (i32, i32, Client) {
 
     let create_recipe_response = client
         .post("/api/recipes")
-        .json(&json!({"name": "Recette", "ingredients": [], "steps": []}))
+        .json(&json!({"name": "Recette", "ingredients": ["1 citron"], "steps": []}))
         .dispatch();
 
     let recipe_id = create_recipe_response
@@ -139,10 +139,11 @@ fn add_non_existing_recipe_to_cart_test(create_cart_and_recipe: (i32, i32, Clien
             .into_json::<Errors>()
             .unwrap(),
         Errors {
-            errors: vec![HTTPError {
-                status_code: Status::NotFound,
-                message: format!("No recipe found with id {}", recipe_id + 1)
-            }]
+            errors: vec![http_error(
+                Status::NotFound,
+                "recipe_not_found",
+                &format!("No recipe found with id {}", recipe_id + 1)
+            )]
         }
     );
 }
@@ -161,10 +162,11 @@ fn add_recipe_to_non_existing_cart_test(create_cart_and_recipe: (i32, i32, Clien
             .into_json::<Errors>()
             .unwrap(),
         Errors {
-            errors: vec![HTTPError {
-                status_code: Status::NotFound,
-                message: format!("No cart found with id {}", cart_id + 1)
-            }]
+            errors: vec![http_error(
+                Status::NotFound,
+                "cart_not_found",
+                &format!("No cart found with id {}", cart_id + 1)
+            )]
         }
     );
 }
@@ -174,13 +176,13 @@ fn add_existing_recipe_to_cart_test(create_cart_and_recipe: (i32, i32, Client))
     let (cart_id, recipe_id, client) = create_cart_and_recipe;
 
     let add_recipe_to_cart_response = client
-        .post(format!("/api/carts/{}/recipes/{}", cart_id, recipe_id))
+        .post(format!("/api/carts/{cart_id}/recipes/{recipe_id}"))
         .dispatch();
 
     assert_eq!(add_recipe_to_cart_response.status(), Status::Created);
 
     let add_existing_recipe_to_cart_response = client
-        .post(format!("/api/carts/{}/recipes/{}", cart_id, recipe_id))
+        .post(format!("/api/carts/{cart_id}/recipes/{recipe_id}"))
         .dispatch();
 
     assert_eq!(
@@ -192,10 +194,11 @@ fn add_existing_recipe_to_cart_test(create_cart_and_recipe: (i32, i32, Client))
             .into_json::<Errors>()
             .unwrap(),
         Errors {
-            errors: vec![HTTPError {
-                status_code: Status::Conflict,
-                message: format!("Recipe with id {recipe_id} is already in cart with id {cart_id}")
-            }]
+            errors: vec![http_error(
+                Status::Conflict,
+                "recipe_already_in_cart",
+                &format!("Recipe with id {recipe_id} is already in cart with id {cart_id}")
+            )]
         }
     );
 }
@@ -0,0 +1,69 @@
+use cooking_book::response::Errors;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rstest::rstest;
+
+mod common;
+use common::client;
+
+#[rstest]
+#[case("/api/unknown", Status::NotFound, "not_found")]
+#[case(
+    "/api/recipes/abc",
+    Status::UnprocessableEntity,
+    "unprocessable_entity"
+)]
+#[case(
+    "/api/carts/abc/shopping-list",
+    Status::UnprocessableEntity,
+    "unprocessable_entity"
+)]
+fn get_error_test(
+    client: Client,
+    #[case] uri: &str,
+    #[case] expected_status: Status,
+    #[case] expected_code: &str,
+) {
+    let response = client.get(uri).dispatch();
+
+    assert_eq!(response.status(), expected_status);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let errors = response.into_json::<Errors>().unwrap().errors;
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].status_code, expected_status);
+    assert_eq!(errors[0].code, expected_code);
+    assert!(errors[0].message.ends_with(&format!("GET {uri}")));
+}
+
+#[rstest]
+#[case("{\"name\": ", Status::BadRequest, "bad_request")]
+#[case(
+    "{\"name\": \"Tapenade\"}",
+    Status::UnprocessableEntity,
+    "unprocessable_entity"
+)]
+#[case("[1, 2, 3]", Status::UnprocessableEntity, "unprocessable_entity")]
+fn malformed_recipe_test(
+    client: Client,
+    #[case] body: &str,
+    #[case] expected_status: Status,
+    #[case] expected_code: &str,
+) {
+    let response = client
+        .post("/api/recipes")
+        .header(ContentType::JSON)
+        .body(body)
+        .dispatch();
+
+    assert_eq!(response.status(), expected_status);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let errors = response.into_json::<Errors>().unwrap().errors;
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].status_code, expected_status);
+    assert_eq!(errors[0].code, expected_code);
+}
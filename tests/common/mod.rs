@@ -1,28 +1,38 @@
-use cooking_book::db::{connect, DBConnection};
+use cooking_book::config::DatabaseSettings;
+use cooking_book::db::{connect, DBConnection, MIGRATIONS};
+use cooking_book::response::HTTPError;
 
-use diesel_migrations::{
-    embed_migrations, EmbeddedMigrations, HarnessWithOutput, MigrationHarness,
-};
+use diesel_migrations::{HarnessWithOutput, MigrationHarness};
+use rocket::http::Status;
 use rocket::local::blocking::Client;
 use rstest::fixture;
 use std::fs;
 use std::path::Path;
 use uuid::Uuid;
 
-pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
-
 #[fixture]
-pub fn create_database_for_test() -> (DBConnection, String) {
+pub fn database_path() -> String {
     let test_db_dir = Path::new("test_db");
 
     assert!(fs::create_dir_all(test_db_dir).is_ok());
 
     let id = Uuid::new_v4();
 
-    let database_path = test_db_dir.join(format!("cooking_book_test_{id}.db"));
+    test_db_dir
+        .join(format!("cooking_book_test_{id}.db"))
+        .into_os_string()
+        .into_string()
+        .unwrap()
+}
 
+#[fixture]
+pub fn create_database_for_test(database_path: String) -> (DBConnection, String) {
     // Establish the connection to the database and make it mutable.
-    let pool = connect(database_path.to_str().unwrap());
+    let pool = connect(&DatabaseSettings {
+        url: database_path.clone(),
+        ..Default::default()
+    })
+    .unwrap();
     let mut connection = DBConnection(pool.get().unwrap());
 
     let stdout = std::io::stdout();
@@ -34,16 +44,26 @@ pub fn create_database_for_test() -> (DBConnection, String) {
     harness.run_pending_migrations(MIGRATIONS).unwrap();
 
     // Return the connection and database path.
-    (
-        connection,
-        database_path.into_os_string().into_string().unwrap(),
-    )
+    (connection, database_path)
 }
 
 #[fixture]
 pub fn client(create_database_for_test: (DBConnection, String)) -> Client {
     let (_, database_url) = create_database_for_test;
 
-    Client::tracked(cooking_book::create_app().manage(connect(&database_url)))
+    let figment = rocket::Config::figment().merge(("database.url", database_url));
+
+    Client::tracked(cooking_book::create_app().configure(figment))
         .expect("expect valid rocket instance")
 }
+
+#[allow(dead_code)]
+pub fn http_error(status_code: Status, code: &str, message: &str) -> HTTPError {
+    HTTPError {
+        status_code,
+        code: code.to_string(),
+        message: message.to_string(),
+        source: None,
+        meta: None,
+    }
+}
@@ -0,0 +1,38 @@
+use cooking_book::config::DatabaseSettings;
+use cooking_book::db::DBConnection;
+
+use rocket::error::ErrorKind;
+use rocket::local::blocking::Client;
+use rstest::rstest;
+
+mod common;
+use common::create_database_for_test;
+
+#[rstest]
+fn database_settings_from_figment_test(create_database_for_test: (DBConnection, String)) {
+    let (_, database_url) = create_database_for_test;
+
+    let figment = rocket::Config::figment()
+        .merge(("database.url", &database_url))
+        .merge(("database.pool_size", 2));
+
+    let client = Client::tracked(cooking_book::create_app().configure(figment))
+        .expect("expect valid rocket instance");
+
+    let settings = client.rocket().state::<DatabaseSettings>().unwrap();
+
+    assert_eq!(settings.url, database_url);
+    assert_eq!(settings.pool_size, 2);
+    assert_eq!(settings.busy_timeout_milliseconds, 5000);
+}
+
+#[rstest]
+fn invalid_database_settings_test() {
+    let figment = rocket::Config::figment().merge(("database.pool_size", 0));
+
+    let Err(error) = Client::tracked(cooking_book::create_app().configure(figment)) else {
+        panic!("expect the database fairing to fail");
+    };
+
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(_)));
+}
@@ -3,6 +3,7 @@ use cooking_book::db_utils::fetch_all_recipes;
 use cooking_book::script::create_recipes;
 
 use rstest::rstest;
+use std::path::Path;
 
 mod common;
 use common::create_database_for_test;
@@ -11,7 +12,7 @@ use common::create_database_for_test;
 fn test_create_recipes(create_database_for_test: (DBConnection, String)) {
     let (mut connection, _) = create_database_for_test;
 
-    create_recipes(&mut connection);
+    create_recipes(&mut connection, Path::new("seeds"));
 
     let all_recipes = fetch_all_recipes(&mut connection).unwrap();
 
@@ -0,0 +1,135 @@
+use cooking_book::db::DBConnection;
+use cooking_book::db_utils::{
+    delete_recipe, insert_cart, insert_into_cart, insert_recipe, search_recipes,
+};
+use cooking_book::models::RecipeIn;
+use cooking_book::schema::{
+    cart_recipes, carts, ingredients, recipe_tags, recipes, step_ingredients, steps, tags,
+};
+
+use diesel::{QueryDsl, RunQueryDsl};
+use rstest::rstest;
+
+mod common;
+use common::create_database_for_test;
+
+fn insert_recipes(connection: &mut DBConnection) {
+    for recipe_in in [
+        RecipeIn {
+            name: "Tarte aux poireaux".to_owned(),
+            ingredients: vec![
+                "3 poireaux".to_owned(),
+                "1 rouleau de pâte brisée".to_owned(),
+            ],
+            steps: vec!["Étaler la pâte brisée.".to_owned()],
+            tags: vec!["Végétarien".to_owned()],
+            ..Default::default()
+        },
+        RecipeIn {
+            name: "Gratin dauphinois".to_owned(),
+            ingredients: vec!["1 kg de pommes de terre".to_owned()],
+            steps: vec!["Enfourner.".to_owned()],
+            tags: vec!["Plat principal".to_owned()],
+            ..Default::default()
+        },
+        RecipeIn {
+            name: "Tarte aux pommes".to_owned(),
+            ingredients: vec![
+                "4 pommes".to_owned(),
+                "1 rouleau de pâte feuilletée".to_owned(),
+            ],
+            steps: vec!["Couper les pommes.".to_owned()],
+            tags: vec!["Dessert".to_owned()],
+            ..Default::default()
+        },
+    ] {
+        insert_recipe(&recipe_in, connection).unwrap();
+    }
+}
+
+#[rstest]
+#[case::name("gratin", vec!["Gratin dauphinois"])]
+#[case::ingredient("poireau", vec!["Tarte aux poireaux"])]
+#[case::tag("vegetarien", vec!["Tarte aux poireaux"])]
+#[case::multi_word_tag("principal", vec!["Gratin dauphinois"])]
+#[case::accents_and_plurals("Pâtes", vec!["Tarte aux poireaux", "Tarte aux pommes"])]
+#[case::every_word_matches("tarte pomme", vec!["Tarte aux pommes"])]
+#[case::words_across_fields("dessert feuilletée", vec!["Tarte aux pommes"])]
+#[case::partial_word("pom", vec![])]
+#[case::no_match("chocolat", vec![])]
+#[case::empty_query("", vec!["Tarte aux poireaux", "Gratin dauphinois", "Tarte aux pommes"])]
+fn search_recipes_test(
+    create_database_for_test: (DBConnection, String),
+    #[case] query: &str,
+    #[case] expected_names: Vec<&str>,
+) {
+    let (mut connection, _) = create_database_for_test;
+    insert_recipes(&mut connection);
+
+    let recipes = search_recipes(query, &mut connection).unwrap();
+
+    assert_eq!(
+        recipes
+            .iter()
+            .map(|recipe| recipe.name.as_str())
+            .collect::<Vec<_>>(),
+        expected_names
+    );
+}
+
+#[rstest]
+fn search_recipes_returns_full_recipes_test(create_database_for_test: (DBConnection, String)) {
+    let (mut connection, _) = create_database_for_test;
+    insert_recipes(&mut connection);
+
+    let recipes = search_recipes("poireaux", &mut connection).unwrap();
+
+    assert_eq!(recipes.len(), 1);
+    assert_eq!(recipes[0].ingredients.len(), 2);
+    assert_eq!(recipes[0].steps.len(), 1);
+    assert_eq!(recipes[0].tags, vec!["végétarien"]);
+}
+
+#[rstest]
+fn delete_recipe_test(create_database_for_test: (DBConnection, String)) {
+    let (mut connection, _) = create_database_for_test;
+    insert_recipes(&mut connection);
+
+    let cart = insert_cart(&mut connection).unwrap();
+    insert_into_cart(cart.id, 1, &mut connection).unwrap();
+    insert_into_cart(cart.id, 2, &mut connection).unwrap();
+
+    assert_eq!(delete_recipe(1, &mut connection).unwrap(), 1);
+
+    assert_eq!(recipes::table.count().get_result(&mut *connection), Ok(2));
+    assert_eq!(steps::table.count().get_result(&mut *connection), Ok(2));
+    assert_eq!(
+        ingredients::table.count().get_result(&mut *connection),
+        Ok(3)
+    );
+    assert_eq!(
+        step_ingredients::table.count().get_result(&mut *connection),
+        Ok(1)
+    );
+    assert_eq!(
+        recipe_tags::table.count().get_result(&mut *connection),
+        Ok(2)
+    );
+    assert_eq!(
+        cart_recipes::table
+            .select(cart_recipes::recipe_id)
+            .load::<i32>(&mut *connection),
+        Ok(vec![2])
+    );
+    assert_eq!(carts::table.count().get_result(&mut *connection), Ok(1));
+    assert_eq!(tags::table.count().get_result(&mut *connection), Ok(3));
+}
+
+#[rstest]
+fn delete_missing_recipe_test(create_database_for_test: (DBConnection, String)) {
+    let (mut connection, _) = create_database_for_test;
+    insert_recipes(&mut connection);
+
+    assert_eq!(delete_recipe(42, &mut connection).unwrap(), 0);
+    assert_eq!(recipes::table.count().get_result(&mut *connection), Ok(3));
+}
@@ -0,0 +1,335 @@
+use cooking_book::models::{CartWithRecipesOut, Data, RecipeWithIngredientsOut};
+use cooking_book::response::Errors;
+
+use rocket::http::{Accept, ContentType, MediaType, Status};
+use rocket::local::blocking::Client;
+use rstest::{fixture, rstest};
+use serde_json::{json, Value};
+
+mod common;
+use common::{client, http_error};
+
+#[fixture]
+fn create_recipe(client: Client) -> (RecipeWithIngredientsOut, Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Saucisses aux lentilles",
+                "ingredients": ["350 g de Lentilles vertes", "1 oignon", "2 gousse d'ail"],
+                "steps": [
+                    "Eplucher et émincer l'oignon.",
+                    "Faire cuire pendant 40 minutes sur feu moyen."
+                ],
+                "description": "Un plat d'hiver",
+                "prep_time_minutes": 10,
+                "cook_time_minutes": 40,
+                "source_url": "https://www.example.com/saucisses-lentilles",
+                "tags": ["hiver", "plat principal"]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    (recipe, client)
+}
+
+#[rstest]
+fn export_recipe_as_jsonld_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let export_response = client
+        .get(format!("/api/recipes/{}.jsonld", recipe.id))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(
+        export_response.content_type(),
+        Some(ContentType::new("application", "ld+json"))
+    );
+    assert_eq!(
+        export_response.into_json::<Value>().unwrap(),
+        json!({
+            "@context": "https://schema.org",
+            "@type": "Recipe",
+            "name": "Saucisses aux lentilles",
+            "description": "Un plat d'hiver",
+            "prepTime": "PT10M",
+            "cookTime": "PT40M",
+            "totalTime": "PT50M",
+            "isBasedOn": "https://www.example.com/saucisses-lentilles",
+            "keywords": "hiver, plat principal",
+            "recipeIngredient": ["350 g de Lentilles vertes", "1 oignon", "2 gousses d'ail"],
+            "recipeInstructions": [
+                {"@type": "HowToStep", "position": 1, "text": "Eplucher et émincer l'oignon."},
+                {
+                    "@type": "HowToStep",
+                    "position": 2,
+                    "text": "Faire cuire pendant 40 minutes sur feu moyen."
+                }
+            ]
+        })
+    );
+}
+
+#[rstest]
+fn export_and_import_jsonld_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let mut exported_recipe = client
+        .get(format!("/api/recipes/{}.jsonld", recipe.id))
+        .dispatch()
+        .into_json::<Value>()
+        .unwrap();
+
+    exported_recipe["name"] = json!("Saucisses aux lentilles (copie)");
+
+    let import_response = client
+        .post("/api/recipes/import")
+        .body(exported_recipe.to_string())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Created);
+
+    let imported_recipe = import_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(imported_recipe.ingredients.len(), recipe.ingredients.len());
+    for (imported, original) in imported_recipe.ingredients.iter().zip(&recipe.ingredients) {
+        assert_eq!(
+            (&imported.name, imported.quantity, &imported.display),
+            (&original.name, original.quantity, &original.display)
+        );
+    }
+    assert_eq!(imported_recipe.prep_time_minutes, recipe.prep_time_minutes);
+    assert_eq!(imported_recipe.cook_time_minutes, recipe.cook_time_minutes);
+    assert_eq!(imported_recipe.tags, recipe.tags);
+}
+
+#[rstest]
+fn export_recipe_as_pdf_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let export_response = client
+        .get(format!("/api/recipes/{}.pdf", recipe.id))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::PDF));
+
+    let pdf = export_response.into_bytes().unwrap();
+
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.ends_with(b"%%EOF"));
+    assert!(pdf
+        .windows(23)
+        .any(|window| window == b"Saucisses aux lentilles"));
+    assert!(pdf.windows(16).any(|window| window == b"/WinAnsiEncoding"));
+}
+
+#[rstest]
+fn export_non_existing_recipe_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    assert_eq!(
+        client
+            .get(format!("/api/recipes/{}.jsonld", recipe.id + 1))
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        client
+            .get(format!("/api/recipes/{}.docx", recipe.id))
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+}
+
+#[rstest]
+fn export_recipe_as_markdown_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let expected_markdown = "# Saucisses aux lentilles
+
+Un plat d'hiver
+
+*Préparation : 10 min · Cuisson : 40 min*
+
+Tags : hiver, plat principal
+
+## Ingrédients
+
+- 350 g de Lentilles vertes
+- 1 oignon
+- 2 gousses d'ail
+
+## Étapes
+
+1. Eplucher et émincer l'oignon.
+2. Faire cuire pendant 40 minutes sur feu moyen.
+
+Source : <https://www.example.com/saucisses-lentilles>
+";
+
+    let export_response = client
+        .get(format!("/api/recipes/{}", recipe.id))
+        .header(Accept::new([MediaType::Markdown.into()]))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::Markdown));
+    assert_eq!(export_response.into_string().unwrap(), expected_markdown);
+
+    let export_response = client
+        .get(format!("/api/recipes/{}.md", recipe.id))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.into_string().unwrap(), expected_markdown);
+}
+
+#[rstest]
+fn export_recipe_as_plain_text_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let export_response = client
+        .get(format!("/api/recipes/{}", recipe.id))
+        .header(Accept::Text)
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::Plain));
+    assert_eq!(
+        export_response.into_string().unwrap(),
+        "Saucisses aux lentilles
+=======================
+
+Un plat d'hiver
+
+Préparation : 10 min · Cuisson : 40 min
+
+Ingrédients
+-----------
+
+  * 350 g de Lentilles vertes
+  * 1 oignon
+  * 2 gousses d'ail
+
+Étapes
+------
+
+  1. Eplucher et émincer l'oignon.
+  2. Faire cuire pendant 40 minutes sur feu moyen.
+
+Source : https://www.example.com/saucisses-lentilles
+"
+    );
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some(Accept::JSON))]
+#[case(Some(Accept::HTML))]
+fn retrieve_recipe_defaults_to_json_test(
+    create_recipe: (RecipeWithIngredientsOut, Client),
+    #[case] accept: Option<Accept>,
+) {
+    let (recipe, client) = create_recipe;
+
+    let mut request = client.get(format!("/api/recipes/{}", recipe.id));
+
+    if let Some(accept) = accept {
+        request = request.header(accept);
+    }
+
+    let retrieve_response = request.dispatch();
+
+    assert_eq!(retrieve_response.status(), Status::Ok);
+    assert_eq!(retrieve_response.content_type(), Some(ContentType::JSON));
+    assert_eq!(
+        retrieve_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data,
+        recipe
+    );
+}
+
+#[rstest]
+fn export_non_existing_recipe_as_markdown_test(client: Client) {
+    let export_response = client
+        .get("/api/recipes/1")
+        .header(Accept::new([MediaType::Markdown.into()]))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::NotFound);
+    assert_eq!(
+        export_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::NotFound,
+                "recipe_not_found",
+                "No recipe found with id 1"
+            )]
+        }
+    );
+}
+
+#[rstest]
+fn export_cart_as_markdown_test(create_recipe: (RecipeWithIngredientsOut, Client)) {
+    let (recipe, client) = create_recipe;
+
+    let cart_id = client
+        .post("/api/carts")
+        .dispatch()
+        .into_json::<Data<CartWithRecipesOut>>()
+        .unwrap()
+        .data
+        .id;
+
+    let empty_export = client
+        .get(format!("/api/carts/{cart_id}/export.md"))
+        .dispatch();
+
+    assert_eq!(empty_export.status(), Status::Ok);
+    assert_eq!(
+        empty_export.into_string().unwrap(),
+        format!("# Panier {cart_id}\n\n*Aucune recette dans ce panier.*\n")
+    );
+
+    client
+        .post(format!("/api/carts/{cart_id}/recipes/{}", recipe.id))
+        .dispatch();
+
+    let export_response = client
+        .get(format!("/api/carts/{cart_id}/export.md"))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::Markdown));
+
+    let markdown = export_response.into_string().unwrap();
+
+    assert!(markdown.starts_with(&format!(
+        "# Panier {cart_id}\n\n## Saucisses aux lentilles\n"
+    )));
+    assert!(markdown.contains("\n### Ingrédients\n\n- 350 g de Lentilles vertes\n"));
+
+    assert_eq!(
+        client
+            .get(format!("/api/carts/{}/export.md", cart_id + 1))
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+}
@@ -0,0 +1,106 @@
+use cooking_book::db::{Pool, MIGRATIONS};
+use cooking_book::models::{Data, HealthOut, PoolStatsOut, ReadinessOut};
+use cooking_book::response::{Errors, RETRY_AFTER_SECONDS};
+
+use diesel::migration::MigrationSource;
+use diesel::sqlite::Sqlite;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rstest::rstest;
+
+mod common;
+use common::{client, database_path};
+
+#[rstest]
+fn health_test(client: Client) {
+    let health_response = client.get("/health").dispatch();
+
+    assert_eq!(health_response.status(), Status::Ok);
+    assert_eq!(
+        health_response.into_json::<Data<HealthOut>>().unwrap(),
+        Data {
+            data: HealthOut {
+                status: "ok".to_string()
+            }
+        }
+    );
+}
+
+fn latest_migration_version() -> Option<String> {
+    MigrationSource::<Sqlite>::migrations(&MIGRATIONS)
+        .unwrap()
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .max()
+}
+
+#[rstest]
+fn ready_test(client: Client) {
+    let ready_response = client.get("/ready").dispatch();
+
+    assert_eq!(ready_response.status(), Status::Ok);
+    assert_eq!(
+        ready_response.into_json::<Data<ReadinessOut>>().unwrap(),
+        Data {
+            data: ReadinessOut {
+                status: "ready".to_string(),
+                schema_version: latest_migration_version(),
+                pool: PoolStatsOut {
+                    max_size: 10,
+                    connections: 10,
+                    idle_connections: 10
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn not_ready_with_pending_migrations_test(database_path: String) {
+    let figment = rocket::Config::figment()
+        .merge(("database.url", database_path))
+        .merge(("database.migrate", false));
+
+    let client = Client::tracked(cooking_book::create_app().configure(figment))
+        .expect("expect valid rocket instance");
+
+    let ready_response = client.get("/ready").dispatch();
+
+    assert_eq!(ready_response.status(), Status::ServiceUnavailable);
+
+    let errors = ready_response.into_json::<Errors>().unwrap().errors;
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].status_code, Status::ServiceUnavailable);
+    assert_eq!(errors[0].code, "pending_migrations");
+    assert!(errors[0]
+        .message
+        .starts_with("Pending migrations: 20240907202820, "));
+}
+
+#[rstest]
+fn pool_exhausted_test(database_path: String) {
+    let figment = rocket::Config::figment()
+        .merge(("database.url", database_path))
+        .merge(("database.pool_size", 1))
+        .merge(("database.connection_timeout_seconds", 1));
+
+    let client = Client::tracked(cooking_book::create_app().configure(figment))
+        .expect("expect valid rocket instance");
+
+    let _connection = client.rocket().state::<Pool>().unwrap().get().unwrap();
+
+    let recipes_response = client.get("/api/recipes").dispatch();
+
+    assert_eq!(recipes_response.status(), Status::ServiceUnavailable);
+    assert_eq!(
+        recipes_response.headers().get_one("Retry-After"),
+        Some(RETRY_AFTER_SECONDS.to_string().as_str())
+    );
+
+    let errors = recipes_response.into_json::<Errors>().unwrap().errors;
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].status_code, Status::ServiceUnavailable);
+    assert_eq!(errors[0].code, "database_unavailable");
+}
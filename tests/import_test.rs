@@ -0,0 +1,340 @@
+use cooking_book::models::{Data, RecipeWithIngredientsOut};
+use cooking_book::response::{Errors, HTTPError};
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::json;
+use rstest::rstest;
+use std::fs;
+
+mod common;
+use common::{client, http_error};
+
+#[rstest]
+fn import_recipe_from_html_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import")
+        .header(ContentType::HTML)
+        .body(fs::read_to_string("tests/fixtures/tapenade.html").unwrap())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Created);
+
+    let recipe = import_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(recipe.name, "Tapenade : la meilleure recette");
+    assert_eq!(
+        recipe.description,
+        Some("Une tapenade maison pour l'apéritif.".to_string())
+    );
+    assert_eq!(recipe.prep_time_minutes, Some(10));
+    assert_eq!(recipe.cook_time_minutes, Some(0));
+    assert_eq!(
+        recipe.source_url,
+        Some("https://www.example.com/recettes/tapenade".to_string())
+    );
+    assert_eq!(
+        recipe.notes,
+        Some("Ingrédients sans quantité : huile d'olive".to_string())
+    );
+    assert_eq!(
+        recipe.tags,
+        vec!["apéritif", "facile", "olive", "provençale", "tapenade"]
+    );
+    assert_eq!(
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| (ingredient.quantity, ingredient.name.as_str()))
+            .collect::<Vec<(f32, &str)>>(),
+        vec![
+            (200.0, "olive noir"),
+            (8.0, "câpres"),
+            (5.0, "anchois à l'huile"),
+            (1.0, "ail")
+        ]
+    );
+    assert_eq!(
+        recipe
+            .steps
+            .iter()
+            .map(|step| step.description.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "Hacher finement la gousse d'ail.",
+            "Mettre dans le bol d'un mixeur les filets d'anchois, les câpres, la gousse d'ail hachée, les olives noires et l'huile d'olive et mixer assez fin."
+        ]
+    );
+}
+
+#[rstest]
+fn import_recipe_from_jsonld_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import")
+        .header(ContentType::JSON)
+        .body(fs::read_to_string("tests/fixtures/gratin.jsonld").unwrap())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Created);
+
+    let recipe = import_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(recipe.name, "Gratin de gnocchi au saumon et épinards");
+    assert_eq!(recipe.prep_time_minutes, Some(15));
+    assert_eq!(recipe.cook_time_minutes, Some(60));
+    assert_eq!(
+        recipe.source_url,
+        Some("https://www.example.com/recettes/gratin-gnocchi".to_string())
+    );
+    assert_eq!(recipe.ingredients.len(), 3);
+    assert_eq!(recipe.steps.len(), 2);
+    assert_eq!(recipe.passive_time_minutes, 30);
+
+    let import_again_response = client
+        .post("/api/recipes/import")
+        .body(fs::read_to_string("tests/fixtures/gratin.jsonld").unwrap())
+        .dispatch();
+
+    assert_eq!(import_again_response.status(), Status::Conflict);
+}
+
+#[rstest]
+fn import_document_without_recipe_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import")
+        .header(ContentType::HTML)
+        .body("<html><head><title>Accueil</title></head></html>")
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        import_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::UnprocessableEntity,
+                "no_recipe_found",
+                "No recipe found in document"
+            )]
+        }
+    );
+}
+
+#[rstest]
+fn create_recipe_from_markdown_test(client: Client) {
+    let create_response = client
+        .post("/api/recipes")
+        .header(ContentType::Markdown)
+        .body(fs::read_to_string("tests/fixtures/saucisses.md").unwrap())
+        .dispatch();
+
+    assert_eq!(create_response.status(), Status::Created);
+
+    let recipe = create_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(recipe.name, "Saucisses aux lentilles");
+    assert_eq!(
+        recipe.description,
+        Some("Un plat d'hiver tout simple.".to_string())
+    );
+    assert_eq!(
+        recipe.notes,
+        Some("Ingrédients sans quantité : sel et poivre".to_string())
+    );
+    assert_eq!(
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| ingredient.display.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "350 g de lentilles vertes",
+            "4 saucisses de Montbéliard",
+            "1 oignon"
+        ]
+    );
+    assert_eq!(
+        recipe
+            .steps
+            .iter()
+            .map(|step| step.description.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "Éplucher et émincer l'oignon.",
+            "Dans une cocotte, mettre les lentilles, les saucisses et l'oignon. Ajouter 70 cl d'eau.",
+            "Faire cuire pendant 40 minutes sur feu moyen."
+        ]
+    );
+}
+
+#[rstest]
+fn create_recipe_from_markdown_without_steps_test(client: Client) {
+    let create_response = client
+        .post("/api/recipes")
+        .header(ContentType::Markdown)
+        .body("# Tapenade\n\n## Ingrédients\n\n- 8 câpres\n")
+        .dispatch();
+
+    assert_eq!(create_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        create_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::UnprocessableEntity,
+                "missing_field",
+                "Recipe has no steps"
+            )]
+        }
+    );
+}
+
+#[rstest]
+fn import_recipe_from_cooklang_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import?format=cooklang")
+        .header(ContentType::Plain)
+        .body(fs::read_to_string("tests/fixtures/gratin.cook").unwrap())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Created);
+
+    let recipe = import_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(recipe.name, "Gratin de gnocchi au saumon");
+    assert_eq!(recipe.prep_time_minutes, Some(15));
+    assert_eq!(recipe.tags, vec!["gratin", "poisson"]);
+    assert_eq!(
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| ingredient.display.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "400 g de gnocchi",
+            "200 g de pavé de saumon",
+            "30 g de beurre",
+            "30 g de farine",
+            "0,5 litre de lait"
+        ]
+    );
+    assert_eq!(
+        recipe.steps[3].description,
+        "Verser la béchamel sur les gnocchi et le saumon, puis enfourner pour 20 minutes."
+    );
+    assert_eq!(recipe.steps[3].duration_minutes, Some(20));
+}
+
+#[rstest]
+fn import_several_recipes_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import?format=paprika")
+        .header(ContentType::JSON)
+        .body(fs::read_to_string("tests/fixtures/paprika.json").unwrap())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Created);
+
+    let recipes = import_response
+        .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(
+        recipes
+            .iter()
+            .map(|recipe| recipe.name.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["Tapenade", "Houmous"]
+    );
+    assert_eq!(
+        client
+            .get("/api/recipes")
+            .dispatch()
+            .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+            .unwrap()
+            .data
+            .len(),
+        2
+    );
+}
+
+#[rstest]
+fn import_several_recipes_with_duplicate_test(client: Client) {
+    let create_response = client
+        .post("/api/recipes")
+        .json(&json!({"name": "Houmous", "ingredients": ["1 citron"], "steps": ["Presser le citron."]}))
+        .dispatch();
+
+    assert_eq!(create_response.status(), Status::Created);
+
+    let import_response = client
+        .post("/api/recipes/import?format=paprika")
+        .header(ContentType::JSON)
+        .body(fs::read_to_string("tests/fixtures/paprika.json").unwrap())
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::Conflict);
+
+    let errors = import_response.into_json::<Errors>().unwrap().errors;
+
+    assert_eq!(errors[0].code, "duplicate_recipe_name");
+    assert_eq!(errors[0].source.as_deref(), Some("/recipes/1/name"));
+    assert_eq!(
+        client
+            .get("/api/recipes")
+            .dispatch()
+            .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+            .unwrap()
+            .data
+            .len(),
+        1
+    );
+}
+
+#[rstest]
+fn import_recipes_with_unparsable_ingredient_test(client: Client) {
+    let import_response = client
+        .post("/api/recipes/import?format=paprika")
+        .header(ContentType::JSON)
+        .body(
+            json!([
+                {"name": "Tapenade", "ingredients": "8 câpres", "directions": "Mixer."},
+                {"name": "Houmous", "ingredients": "1 citron\nsel", "directions": "Mixer."}
+            ])
+            .to_string(),
+        )
+        .dispatch();
+
+    assert_eq!(import_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        import_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![HTTPError {
+                source: Some("/recipes/1/ingredients/1".to_string()),
+                ..http_error(
+                    Status::UnprocessableEntity,
+                    "invalid_ingredient",
+                    "recipes[1].ingredients[1] is not in the '<quantity> [unit] [de] <name>' format: sel"
+                )
+            }]
+        }
+    );
+    assert!(client
+        .get("/api/recipes")
+        .dispatch()
+        .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+        .unwrap()
+        .data
+        .is_empty());
+}
@@ -0,0 +1,69 @@
+use cooking_book::db::DBConnection;
+
+use diesel::RunQueryDsl;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::tokio::time::{sleep, Instant};
+use rstest::rstest;
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+use common::create_database_for_test;
+
+const LOCK_DURATION: Duration = Duration::from_millis(500);
+const HEALTH_REQUESTS: usize = 20;
+
+#[rstest]
+fn locked_database_does_not_stall_other_requests_test(
+    create_database_for_test: (DBConnection, String),
+) {
+    let (mut lock_connection, database_url) = create_database_for_test;
+
+    rocket::execute(async move {
+        let figment = rocket::Config::figment()
+            .merge(("database.url", database_url))
+            .merge(("database.busy_timeout_milliseconds", 5000));
+
+        let client = Client::tracked(cooking_book::create_app().configure(figment))
+            .await
+            .expect("expect valid rocket instance");
+
+        diesel::sql_query("BEGIN EXCLUSIVE")
+            .execute(&mut *lock_connection)
+            .unwrap();
+
+        let started = Instant::now();
+
+        let (create_recipe_response, health_elapsed, ()) = rocket::tokio::join!(
+            client
+                .post("/api/recipes")
+                .json(&json!(
+                    {
+                        "name": "Tapenade",
+                        "ingredients": ["200 g d'olives noires"],
+                        "steps": ["Mixer les olives."]
+                    }
+                ))
+                .dispatch(),
+            async {
+                for _ in 0..HEALTH_REQUESTS {
+                    assert_eq!(client.get("/health").dispatch().await.status(), Status::Ok);
+                }
+
+                started.elapsed()
+            },
+            async {
+                sleep(LOCK_DURATION).await;
+
+                diesel::sql_query("COMMIT")
+                    .execute(&mut *lock_connection)
+                    .unwrap();
+            }
+        );
+
+        assert_eq!(create_recipe_response.status(), Status::Created);
+        assert!(started.elapsed() >= LOCK_DURATION);
+        assert!(health_elapsed < LOCK_DURATION);
+    });
+}
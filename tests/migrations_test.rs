@@ -0,0 +1,175 @@
+use cooking_book::db::{run_migrations, DBConnection, MIGRATIONS};
+use cooking_book::schema::recipes;
+
+use diesel::{Connection, QueryDsl, RunQueryDsl, SqliteConnection};
+use diesel_migrations::MigrationHarness;
+use rocket::error::ErrorKind;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rstest::rstest;
+
+mod common;
+use common::{create_database_for_test, database_path};
+
+fn figment(database_url: &str, migrate: bool) -> rocket::figment::Figment {
+    rocket::Config::figment()
+        .merge(("database.url", database_url))
+        .merge(("database.migrate", migrate))
+}
+
+#[rstest]
+fn migrate_on_launch_test(database_path: String) {
+    let client =
+        Client::tracked(cooking_book::create_app().configure(figment(&database_path, true)))
+            .expect("expect valid rocket instance");
+
+    assert_eq!(client.get("/api/recipes").dispatch().status(), Status::Ok);
+}
+
+#[rstest]
+fn launch_without_migrations_test(database_path: String) {
+    let client =
+        Client::tracked(cooking_book::create_app().configure(figment(&database_path, false)))
+            .expect("expect valid rocket instance");
+
+    assert_eq!(
+        client.get("/api/recipes").dispatch().status(),
+        Status::InternalServerError
+    );
+}
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn refuse_newer_schema_test(
+    create_database_for_test: (DBConnection, String),
+    #[case] migrate: bool,
+) {
+    let (mut connection, database_url) = create_database_for_test;
+
+    diesel::sql_query("INSERT INTO __diesel_schema_migrations (version) VALUES ('29991231000000')")
+        .execute(&mut *connection)
+        .unwrap();
+
+    let Err(error) =
+        Client::tracked(cooking_book::create_app().configure(figment(&database_url, migrate)))
+    else {
+        panic!("expect the migrations fairing to fail");
+    };
+
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(_)));
+}
+
+fn database_before_normalized_names(
+    create_database_for_test: (DBConnection, String),
+    names: &[&str],
+) -> DBConnection {
+    let (mut connection, _) = create_database_for_test;
+
+    connection.revert_last_migration(MIGRATIONS).unwrap();
+
+    for name in names {
+        diesel::sql_query("INSERT INTO recipes (name) VALUES (?)")
+            .bind::<diesel::sql_types::Text, _>(name)
+            .execute(&mut *connection)
+            .unwrap();
+    }
+
+    connection
+}
+
+#[rstest]
+fn backfill_normalized_names_test(create_database_for_test: (DBConnection, String)) {
+    let mut connection =
+        database_before_normalized_names(create_database_for_test, &["Gâteau", "Tapenade "]);
+
+    assert_eq!(
+        run_migrations(&mut connection).unwrap(),
+        vec!["20241020090000"]
+    );
+    assert_eq!(
+        recipes::table
+            .select(recipes::normalized_name)
+            .order(recipes::id)
+            .load::<String>(&mut *connection)
+            .unwrap(),
+        vec!["gateau", "tapenade"]
+    );
+}
+
+#[rstest]
+fn refuse_colliding_names_test(create_database_for_test: (DBConnection, String)) {
+    let mut connection = database_before_normalized_names(
+        create_database_for_test,
+        &["Tapenade", "Houmous", "tapenade ", "TAPENADE"],
+    );
+
+    let error = run_migrations(&mut connection).unwrap_err().to_string();
+
+    assert!(error.ends_with("\"Tapenade\", \"tapenade \", \"TAPENADE\""));
+    assert!(recipes::table
+        .select(recipes::normalized_name)
+        .load::<String>(&mut *connection)
+        .is_err());
+}
+
+fn migrate_without_app(database_url: &str, names: &[&str]) {
+    let mut connection = SqliteConnection::establish(database_url).unwrap();
+
+    connection.revert_last_migration(MIGRATIONS).unwrap();
+
+    for name in names {
+        diesel::sql_query("INSERT INTO recipes (name) VALUES (?)")
+            .bind::<diesel::sql_types::Text, _>(name)
+            .execute(&mut connection)
+            .unwrap();
+    }
+
+    connection.run_pending_migrations(MIGRATIONS).unwrap();
+}
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn backfill_after_external_migration_test(
+    create_database_for_test: (DBConnection, String),
+    #[case] migrate: bool,
+) {
+    let (mut connection, database_url) = create_database_for_test;
+
+    migrate_without_app(&database_url, &["Gâteau", "Tapenade "]);
+
+    let client =
+        Client::tracked(cooking_book::create_app().configure(figment(&database_url, migrate)))
+            .expect("expect valid rocket instance");
+
+    assert_eq!(client.get("/api/recipes").dispatch().status(), Status::Ok);
+    assert_eq!(
+        recipes::table
+            .select(recipes::normalized_name)
+            .order(recipes::id)
+            .load::<String>(&mut *connection)
+            .unwrap(),
+        vec!["gateau", "tapenade"]
+    );
+}
+
+#[rstest]
+fn refuse_colliding_names_after_external_migration_test(
+    create_database_for_test: (DBConnection, String),
+) {
+    let (mut connection, database_url) = create_database_for_test;
+
+    migrate_without_app(&database_url, &["Tapenade", "tapenade "]);
+
+    let error = run_migrations(&mut connection).unwrap_err().to_string();
+
+    assert!(error.ends_with("\"Tapenade\", \"tapenade \""));
+    assert_eq!(
+        recipes::table
+            .select(recipes::normalized_name)
+            .load::<String>(&mut *connection)
+            .unwrap(),
+        vec!["", ""]
+    );
+}
@@ -0,0 +1,155 @@
+use cooking_book::openapi::{undocumented, OPENAPI_VERSION};
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rstest::rstest;
+use serde_json::Value;
+
+mod common;
+use common::client;
+
+fn specification(client: &Client) -> Value {
+    let response = client.get("/api/openapi.json").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    response.into_json::<Value>().unwrap()
+}
+
+#[rstest]
+fn openapi_version_test(client: Client) {
+    assert_eq!(specification(&client)["openapi"], OPENAPI_VERSION);
+}
+
+#[rstest]
+#[case("/health", "get")]
+#[case("/ready", "get")]
+#[case("/api/export", "get")]
+#[case("/api/import", "post")]
+#[case("/api/carts", "post")]
+#[case("/api/carts/{cart_id}", "get")]
+#[case("/api/carts/{cart_id}", "delete")]
+#[case("/api/carts/{cart_id}/recipes/{recipe_id}", "post")]
+#[case("/api/recipes", "get")]
+#[case("/api/recipes", "post")]
+#[case("/api/recipes/{recipe_id}", "get")]
+#[case("/api/recipes/{recipe_id}.{extension}", "get")]
+#[case("/api/recipes/{recipe_id}/steps/{position}", "put")]
+#[case("/api/tags/{tag_id}/merge/{target_tag_id}", "post")]
+fn openapi_paths_test(client: Client, #[case] path: &str, #[case] method: &str) {
+    let operation = &specification(&client)["paths"][path][method];
+
+    assert!(operation["summary"].is_string());
+    assert_eq!(
+        operation["responses"]["default"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Errors"
+    );
+}
+
+#[rstest]
+fn every_route_is_documented_test(client: Client) {
+    assert!(client.rocket().routes().count() > 0);
+    assert_eq!(undocumented(client.rocket().routes()), Vec::<String>::new());
+}
+
+#[rstest]
+fn every_operation_has_a_summary_test(client: Client) {
+    let specification = specification(&client);
+
+    for (path, operations) in specification["paths"].as_object().unwrap() {
+        for (method, operation) in operations.as_object().unwrap() {
+            assert!(
+                operation["summary"]
+                    .as_str()
+                    .is_some_and(|summary| !summary.is_empty()),
+                "{method} {path} has no summary"
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case("RecipeIn")]
+#[case("RecipeWithIngredientsOut")]
+#[case("CartWithRecipesOut")]
+#[case("Data_for_RecipeWithIngredientsOut")]
+#[case("Errors")]
+#[case("HTTPError")]
+fn openapi_schemas_test(client: Client, #[case] schema: &str) {
+    assert_eq!(
+        specification(&client)["components"]["schemas"][schema]["type"],
+        "object"
+    );
+}
+
+#[rstest]
+fn openapi_request_and_response_test(client: Client) {
+    let operation = &specification(&client)["paths"]["/api/recipes"]["post"];
+
+    assert_eq!(
+        operation["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/RecipeIn"
+    );
+    assert!(operation["requestBody"]["content"]["text/markdown"].is_object());
+    assert_eq!(
+        operation["responses"]["201"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Data_for_RecipeWithIngredientsOut"
+    );
+}
+
+#[rstest]
+fn openapi_parameters_test(client: Client) {
+    let specification = specification(&client);
+    let parameters = specification["paths"]["/api/recipes"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+
+    assert_eq!(
+        parameters
+            .iter()
+            .map(|parameter| (
+                parameter["name"].as_str().unwrap(),
+                parameter["in"].as_str().unwrap()
+            ))
+            .collect::<Vec<_>>(),
+        vec![("tag", "query"), ("sort", "query")]
+    );
+    assert_eq!(
+        specification["paths"]["/api/carts/{cart_id}"]["get"]["parameters"][0]["schema"]["type"],
+        "integer"
+    );
+}
+
+#[rstest]
+fn docs_test(client: Client) {
+    let response = client.get("/api/docs").dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    let page = response.into_string().unwrap();
+
+    assert!(page.contains("/api/openapi.json"));
+    assert!(!page.contains("https://"));
+}
+
+#[rstest]
+#[case(
+    "/api/docs/swagger-ui-bundle.js",
+    ContentType::JavaScript,
+    "SwaggerUIBundle"
+)]
+#[case("/api/docs/swagger-ui.css", ContentType::CSS, ".swagger-ui")]
+fn docs_assets_test(
+    client: Client,
+    #[case] uri: &str,
+    #[case] expected_content_type: ContentType,
+    #[case] expected_content: &str,
+) {
+    let response = client.get(uri).dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(expected_content_type));
+    assert!(response.into_string().unwrap().contains(expected_content));
+}
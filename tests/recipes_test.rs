@@ -1,4 +1,4 @@
-use cooking_book::models::{Data, IngredientOut, RecipeWithIngredientsOut};
+use cooking_book::models::{Data, IngredientOut, RecipeWithIngredientsOut, StepOut};
 use cooking_book::response::{Errors, HTTPError};
 
 use rocket::http::Status;
@@ -7,7 +7,7 @@ use rstest::rstest;
 use serde_json::json;
 
 mod common;
-use common::client;
+use common::{client, http_error};
 
 #[rstest]
 fn create_and_retrieve_recipe(client: Client) {
@@ -43,8 +43,19 @@ fn create_and_retrieve_recipe(client: Client) {
     assert_eq!(recipe_out.data.ingredients[0].unit, "g".to_string());
     assert_eq!(
         recipe_out.data.steps,
-        vec!["Mettre le saumon dans la poele. Cuire à feu doux pendant 10 minutes.".to_string()],
+        vec![StepOut {
+            id: 1,
+            position: 0,
+            description: "Mettre le saumon dans la poele. Cuire à feu doux pendant 10 minutes."
+                .to_string(),
+            duration_minutes: Some(10),
+            temperature_celsius: None,
+            passive: true,
+            ingredient_ids: vec![1],
+        }],
     );
+    assert_eq!(recipe_out.data.active_time_minutes, 0);
+    assert_eq!(recipe_out.data.passive_time_minutes, 10);
 
     let recipe_id = recipe_out.data.id;
 
@@ -66,16 +77,20 @@ fn recipe_not_found_test(client: Client) {
     assert_eq!(
         not_found_recipe_response.into_json::<Errors>().unwrap(),
         Errors {
-            errors: vec![HTTPError {
-                status_code: Status::NotFound,
-                message: "No recipe found with id 1".to_string()
-            }]
+            errors: vec![http_error(
+                Status::NotFound,
+                "recipe_not_found",
+                "No recipe found with id 1"
+            )]
         }
     );
 }
 
 #[rstest]
-fn recipe_already_exist_test(client: Client) {
+#[case("Saumon fumé à la poele")]
+#[case(" saumon fumé à la poêle ")]
+#[case("SAUMON  FUME A LA POELE")]
+fn recipe_already_exist_test(client: Client, #[case] duplicate_name: &str) {
     let recipe_in = json!(
         {
             "name": "Saumon fumé à la poele",
@@ -88,8 +103,13 @@ fn recipe_already_exist_test(client: Client) {
 
     assert_eq!(create_recipe_response.status(), Status::Created);
 
-    let create_recipe_already_exist_response =
-        client.post("/api/recipes").json(&recipe_in).dispatch();
+    let mut duplicate_recipe_in = recipe_in.clone();
+    duplicate_recipe_in["name"] = json!(duplicate_name);
+
+    let create_recipe_already_exist_response = client
+        .post("/api/recipes")
+        .json(&duplicate_recipe_in)
+        .dispatch();
 
     assert_eq!(
         create_recipe_already_exist_response.status(),
@@ -102,8 +122,13 @@ fn recipe_already_exist_test(client: Client) {
             .unwrap(),
         Errors {
             errors: vec![HTTPError {
-                status_code: Status::Conflict,
-                message: "Recipe already exists: Saumon fumé à la poele".to_string()
+                source: Some("/name".to_string()),
+                meta: Some(json!({"recipe_id": 1})),
+                ..http_error(
+                    Status::Conflict,
+                    "duplicate_recipe_name",
+                    &format!("Recipe already exists: {}", duplicate_name.trim())
+                )
             }]
         }
     );
@@ -156,28 +181,377 @@ fn recipe_fetch_all_recipes_test(client: Client) {
                 RecipeWithIngredientsOut {
                     id: 1,
                     name: "Recette 1".to_string(),
+                    description: None,
+                    prep_time_minutes: None,
+                    cook_time_minutes: None,
+                    difficulty: None,
+                    source_url: None,
+                    notes: None,
+                    tags: Vec::new(),
                     ingredients: vec![IngredientOut {
                         id: 1,
                         preposition: "de ".to_string(),
                         name: "sucre".to_string(),
                         quantity: 20.0,
-                        unit: "g".to_string()
+                        unit: "g".to_string(),
+                        display: "20 g de sucre".to_string(),
                     }],
-                    steps: vec!["Etape 1".to_string()]
+                    steps: vec![StepOut {
+                        id: 1,
+                        position: 0,
+                        description: "Etape 1".to_string(),
+                        duration_minutes: None,
+                        temperature_celsius: None,
+                        passive: false,
+                        ingredient_ids: Vec::new(),
+                    }],
+                    active_time_minutes: 0,
+                    passive_time_minutes: 0,
                 },
                 RecipeWithIngredientsOut {
                     id: 2,
                     name: "Recette 2".to_string(),
+                    description: None,
+                    prep_time_minutes: None,
+                    cook_time_minutes: None,
+                    difficulty: None,
+                    source_url: None,
+                    notes: None,
+                    tags: Vec::new(),
                     ingredients: vec![IngredientOut {
                         id: 2,
                         preposition: "de ".to_string(),
                         name: "lait".to_string(),
                         quantity: 30.0,
-                        unit: "mL".to_string()
+                        unit: "mL".to_string(),
+                        display: "30 mL de lait".to_string(),
+                    }],
+                    steps: vec![StepOut {
+                        id: 2,
+                        position: 0,
+                        description: "Etape 2".to_string(),
+                        duration_minutes: None,
+                        temperature_celsius: None,
+                        passive: false,
+                        ingredient_ids: Vec::new(),
                     }],
-                    steps: vec!["Etape 2".to_string()]
+                    active_time_minutes: 0,
+                    passive_time_minutes: 0,
                 }
             ]
         }
     );
 }
+
+#[rstest]
+fn recipe_times_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Gratin",
+                "ingredients": ["400g de gnocchi"],
+                "steps": [
+                    "Préchauffer le four à 180°C.",
+                    "Mélanger pendant 5 minutes.",
+                    "Enfourner pour 1h10 à 180°C."
+                ]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe_out = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap();
+
+    assert_eq!(recipe_out.data.active_time_minutes, 5);
+    assert_eq!(recipe_out.data.passive_time_minutes, 70);
+
+    let timings: Vec<(Option<i32>, Option<i32>, bool)> = recipe_out
+        .data
+        .steps
+        .iter()
+        .map(|step| {
+            (
+                step.duration_minutes,
+                step.temperature_celsius,
+                step.passive,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        timings,
+        vec![
+            (None, Some(180), false),
+            (Some(5), None, false),
+            (Some(70), Some(180), true)
+        ]
+    );
+}
+
+#[rstest]
+fn create_recipe_with_huge_durations_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Levain",
+                "ingredients": ["100 g de farine"],
+                "steps": [
+                    "Laisser reposer 99999999 heures.",
+                    "Laisser reposer 2000000000 minutes.",
+                    "Laisser reposer 2000000000 minutes."
+                ]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe_out = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap();
+
+    assert_eq!(recipe_out.data.steps[0].duration_minutes, None);
+    assert_eq!(
+        recipe_out.data.steps[1].duration_minutes,
+        Some(2_000_000_000)
+    );
+    assert_eq!(recipe_out.data.passive_time_minutes, i32::MAX);
+}
+
+#[rstest]
+fn recipe_sort_by_total_time_test(client: Client) {
+    for (name, step) in [
+        ("Recette longue", "Cuire 2 heures."),
+        ("Recette rapide", "Cuire 10 minutes."),
+        ("Recette moyenne", "Cuire 45 minutes."),
+    ] {
+        let create_recipe_response = client
+            .post("/api/recipes")
+            .json(&json!({"name": name, "ingredients": ["1 citron"], "steps": [step]}))
+            .dispatch();
+
+        assert_eq!(create_recipe_response.status(), Status::Created);
+    }
+
+    let response_fetch_all_recipes = client.get("/api/recipes?sort=total_time").dispatch();
+
+    assert_eq!(response_fetch_all_recipes.status(), Status::Ok);
+
+    let recipe_names: Vec<String> = response_fetch_all_recipes
+        .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+        .unwrap()
+        .data
+        .into_iter()
+        .map(|recipe| recipe.name)
+        .collect();
+
+    assert_eq!(
+        recipe_names,
+        vec!["Recette rapide", "Recette moyenne", "Recette longue"]
+    );
+}
+
+#[rstest]
+fn create_recipe_with_metadata_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Tapenade",
+                "ingredients": ["200g d'olive noir"],
+                "steps": ["Mixer assez fin."],
+                "description": "Une tapenade maison",
+                "prep_time_minutes": 10,
+                "cook_time_minutes": 0,
+                "difficulty": "très facile",
+                "source_url": "https://www.marmiton.org/recettes/recette_tapenade_11219.aspx",
+                "notes": "Se conserve une semaine au frais"
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe_out = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap();
+
+    assert_eq!(
+        recipe_out.data.description,
+        Some("Une tapenade maison".to_string())
+    );
+    assert_eq!(recipe_out.data.prep_time_minutes, Some(10));
+    assert_eq!(recipe_out.data.cook_time_minutes, Some(0));
+    assert_eq!(recipe_out.data.difficulty, Some("très facile".to_string()));
+    assert_eq!(
+        recipe_out.data.source_url,
+        Some("https://www.marmiton.org/recettes/recette_tapenade_11219.aspx".to_string())
+    );
+    assert_eq!(
+        recipe_out.data.notes,
+        Some("Se conserve une semaine au frais".to_string())
+    );
+
+    let retrieve_recipe_response = client
+        .get(format!("/api/recipes/{}", recipe_out.data.id))
+        .dispatch();
+
+    assert_eq!(
+        retrieve_recipe_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap(),
+        recipe_out
+    );
+}
+
+#[rstest]
+fn create_recipe_with_invalid_metadata_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Tapenade",
+                "ingredients": ["200g d'olive noir"],
+                "steps": ["Mixer assez fin."],
+                "prep_time_minutes": -10,
+                "difficulty": "impossible",
+                "source_url": "marmiton"
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        create_recipe_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![
+                HTTPError {
+                    source: Some("/prep_time_minutes".to_string()),
+                    ..http_error(
+                        Status::UnprocessableEntity,
+                        "negative_time",
+                        "prep_time_minutes must be a non-negative number of minutes"
+                    )
+                },
+                HTTPError {
+                    source: Some("/difficulty".to_string()),
+                    ..http_error(
+                        Status::UnprocessableEntity,
+                        "unknown_difficulty",
+                        "difficulty must be one of: très facile, facile, moyenne, difficile"
+                    )
+                },
+                HTTPError {
+                    source: Some("/source_url".to_string()),
+                    ..http_error(
+                        Status::UnprocessableEntity,
+                        "invalid_url",
+                        "source_url is not a valid http(s) URL: marmiton"
+                    )
+                }
+            ]
+        }
+    );
+
+    assert_eq!(
+        client
+            .get("/api/recipes")
+            .dispatch()
+            .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+            .unwrap()
+            .data
+            .len(),
+        0
+    );
+}
+
+#[rstest]
+fn create_recipe_with_unparsable_ingredient_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!({"name": "Salade", "ingredients": ["1 salade", "sel"], "steps": []}))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        create_recipe_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![HTTPError {
+                source: Some("/ingredients/1".to_string()),
+                ..http_error(
+                    Status::UnprocessableEntity,
+                    "invalid_ingredient",
+                    "ingredients[1] is not in the '<quantity> [unit] [de] <name>' format: sel"
+                )
+            }]
+        }
+    );
+}
+
+#[rstest]
+fn create_recipe_with_all_violations_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!({"name": "  ", "ingredients": [], "steps": ["Mixer.", " "]}))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        create_recipe_response
+            .into_json::<Errors>()
+            .unwrap()
+            .errors
+            .into_iter()
+            .map(|error| (error.code, error.source))
+            .collect::<Vec<(String, Option<String>)>>(),
+        vec![
+            ("blank_name".to_string(), Some("/name".to_string())),
+            (
+                "missing_ingredients".to_string(),
+                Some("/ingredients".to_string())
+            ),
+            ("blank_step".to_string(), Some("/steps/1".to_string())),
+        ]
+    );
+}
+
+#[rstest]
+fn create_recipe_trims_name_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!({"name": " Tapenade ", "ingredients": ["8 câpres"], "steps": []}))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+    assert_eq!(
+        create_recipe_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data
+            .name,
+        "Tapenade"
+    );
+}
+
+#[rstest]
+fn create_recipe_without_content_type_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .body(json!({"name": "Tapenade", "ingredients": ["8 câpres"], "steps": []}).to_string())
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+    assert_eq!(
+        create_recipe_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data
+            .name,
+        "Tapenade"
+    );
+}
@@ -0,0 +1,163 @@
+use cooking_book::models::{
+    AisleOut, CartWithRecipesOut, Data, RecipeWithIngredientsOut, ShoppingListItemOut,
+    ShoppingListOut,
+};
+use cooking_book::response::Errors;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rstest::{fixture, rstest};
+use serde_json::{json, Value};
+
+mod common;
+use common::{client, http_error};
+
+fn create_recipe(recipe: &Value, client: &Client) -> i32 {
+    let create_recipe_response = client.post("/api/recipes").json(recipe).dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data
+        .id
+}
+
+#[fixture]
+fn create_cart_with_recipes(client: Client) -> (i32, Client) {
+    let lentils_id = create_recipe(
+        &json!({
+            "name": "Saucisses aux lentilles",
+            "ingredients": [
+                "350 g de Lentilles vertes",
+                "4 saucisses de Montbéliard",
+                "1 oignon",
+                "1 gousse d'ail"
+            ],
+            "steps": ["Faire cuire pendant 40 minutes."]
+        }),
+        &client,
+    );
+    let soup_id = create_recipe(
+        &json!({
+            "name": "Soupe à l'oignon",
+            "ingredients": ["3 oignons", "30 g de beurre", "1 gousse d'ail", "1 pincée de sel"],
+            "steps": ["Faire revenir les oignons dans le beurre."]
+        }),
+        &client,
+    );
+
+    let cart_id = client
+        .post("/api/carts")
+        .dispatch()
+        .into_json::<Data<CartWithRecipesOut>>()
+        .unwrap()
+        .data
+        .id;
+
+    for recipe_id in [lentils_id, soup_id] {
+        let add_recipe_response = client
+            .post(format!("/api/carts/{cart_id}/recipes/{recipe_id}"))
+            .dispatch();
+
+        assert_eq!(add_recipe_response.status(), Status::Created);
+    }
+
+    (cart_id, client)
+}
+
+fn item(name: &str, quantity: f32, unit: &str, display: &str) -> ShoppingListItemOut {
+    ShoppingListItemOut {
+        name: name.to_string(),
+        quantity,
+        unit: unit.to_string(),
+        display: display.to_string(),
+    }
+}
+
+#[rstest]
+fn retrieve_shopping_list_test(create_cart_with_recipes: (i32, Client)) {
+    let (cart_id, client) = create_cart_with_recipes;
+
+    let shopping_list_response = client
+        .get(format!("/api/carts/{cart_id}/shopping-list"))
+        .dispatch();
+
+    assert_eq!(shopping_list_response.status(), Status::Ok);
+    assert_eq!(
+        shopping_list_response
+            .into_json::<Data<ShoppingListOut>>()
+            .unwrap()
+            .data,
+        ShoppingListOut {
+            cart_id,
+            aisles: vec![
+                AisleOut {
+                    name: "Fruits et légumes".to_string(),
+                    items: vec![
+                        item("ail", 2.0, "gousse", "2 gousses d'ail"),
+                        item("oignon", 4.0, "", "4 oignons"),
+                    ],
+                },
+                AisleOut {
+                    name: "Boucherie et poissonnerie".to_string(),
+                    items: vec![item(
+                        "Montbéliard",
+                        4.0,
+                        "saucisses",
+                        "4 saucisses de Montbéliard"
+                    )],
+                },
+                AisleOut {
+                    name: "Crèmerie".to_string(),
+                    items: vec![item("beurre", 30.0, "g", "30 g de beurre")],
+                },
+                AisleOut {
+                    name: "Épicerie".to_string(),
+                    items: vec![
+                        item("Lentilles vertes", 350.0, "g", "350 g de Lentilles vertes"),
+                        item("sel", 1.0, "pincée", "1 pincée de sel"),
+                    ],
+                },
+            ],
+        }
+    );
+}
+
+#[rstest]
+fn export_shopping_list_as_pdf_test(create_cart_with_recipes: (i32, Client)) {
+    let (cart_id, client) = create_cart_with_recipes;
+
+    let export_response = client
+        .get(format!("/api/carts/{cart_id}/shopping-list.pdf"))
+        .dispatch();
+
+    assert_eq!(export_response.status(), Status::Ok);
+    assert_eq!(export_response.content_type(), Some(ContentType::PDF));
+
+    let pdf = export_response.into_bytes().unwrap();
+
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.windows(15).any(|window| window == b"2 gousses d'ail"));
+    assert!(pdf.windows(14).any(|window| window == b"30 g de beurre"));
+}
+
+#[rstest]
+#[case("shopping-list")]
+#[case("shopping-list.pdf")]
+fn shopping_list_of_non_existing_cart_test(client: Client, #[case] resource: &str) {
+    let shopping_list_response = client.get(format!("/api/carts/1/{resource}")).dispatch();
+
+    assert_eq!(shopping_list_response.status(), Status::NotFound);
+    assert_eq!(
+        shopping_list_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::NotFound,
+                "cart_not_found",
+                "No cart found with id 1"
+            )]
+        }
+    );
+}
@@ -0,0 +1,295 @@
+use cooking_book::models::{Data, RecipeWithIngredientsOut};
+use cooking_book::response::{Errors, HTTPError};
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rstest::{fixture, rstest};
+use serde_json::json;
+
+mod common;
+use common::{client, http_error};
+
+#[fixture]
+fn create_recipe_with_steps(client: Client) -> (i32, Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Recette",
+                "ingredients": ["20g de sucre"],
+                "steps": ["Etape 1", "Etape 2", "Etape 3"]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe_id = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data
+        .id;
+
+    (recipe_id, client)
+}
+
+fn retrieve_steps(recipe_id: i32, client: &Client) -> Vec<String> {
+    client
+        .get(format!("/api/recipes/{recipe_id}"))
+        .dispatch()
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data
+        .steps
+        .into_iter()
+        .map(|step| step.description)
+        .collect()
+}
+
+#[rstest]
+#[case(0, vec!["Nouvelle", "Etape 1", "Etape 2", "Etape 3"])]
+#[case(1, vec!["Etape 1", "Nouvelle", "Etape 2", "Etape 3"])]
+#[case(3, vec!["Etape 1", "Etape 2", "Etape 3", "Nouvelle"])]
+fn insert_step_test(
+    create_recipe_with_steps: (i32, Client),
+    #[case] position: i32,
+    #[case] expected_steps: Vec<&str>,
+) {
+    let (recipe_id, client) = create_recipe_with_steps;
+
+    let insert_step_response = client
+        .post(format!("/api/recipes/{recipe_id}/steps/{position}"))
+        .json(&json!({"description": "Nouvelle"}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::Created);
+    assert_eq!(
+        insert_step_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data
+            .steps
+            .iter()
+            .map(|step| step.description.as_str())
+            .collect::<Vec<&str>>(),
+        expected_steps
+    );
+    assert_eq!(retrieve_steps(recipe_id, &client), expected_steps);
+}
+
+#[rstest]
+#[case(0, 2, vec!["Etape 2", "Etape 3", "Etape 1"])]
+#[case(2, 0, vec!["Etape 3", "Etape 1", "Etape 2"])]
+#[case(1, 1, vec!["Etape 1", "Etape 2", "Etape 3"])]
+fn move_step_test(
+    create_recipe_with_steps: (i32, Client),
+    #[case] position: i32,
+    #[case] new_position: i32,
+    #[case] expected_steps: Vec<&str>,
+) {
+    let (recipe_id, client) = create_recipe_with_steps;
+
+    let move_step_response = client
+        .put(format!("/api/recipes/{recipe_id}/steps/{position}"))
+        .json(&json!({"position": new_position}))
+        .dispatch();
+
+    assert_eq!(move_step_response.status(), Status::Ok);
+    assert_eq!(retrieve_steps(recipe_id, &client), expected_steps);
+}
+
+#[rstest]
+fn delete_step_test(create_recipe_with_steps: (i32, Client)) {
+    let (recipe_id, client) = create_recipe_with_steps;
+
+    let delete_step_response = client
+        .delete(format!("/api/recipes/{recipe_id}/steps/0"))
+        .dispatch();
+
+    assert_eq!(delete_step_response.status(), Status::NoContent);
+    assert_eq!(
+        retrieve_steps(recipe_id, &client),
+        vec!["Etape 2", "Etape 3"]
+    );
+
+    let insert_step_response = client
+        .post(format!("/api/recipes/{recipe_id}/steps/2"))
+        .json(&json!({"description": "Etape 4"}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::Created);
+    assert_eq!(
+        retrieve_steps(recipe_id, &client),
+        vec!["Etape 2", "Etape 3", "Etape 4"]
+    );
+}
+
+#[rstest]
+fn step_not_found_test(create_recipe_with_steps: (i32, Client)) {
+    let (recipe_id, client) = create_recipe_with_steps;
+
+    let expected_error = Errors {
+        errors: vec![http_error(
+            Status::NotFound,
+            "step_not_found",
+            &format!("No step found at position 3 in recipe with id {recipe_id}"),
+        )],
+    };
+
+    let delete_step_response = client
+        .delete(format!("/api/recipes/{recipe_id}/steps/3"))
+        .dispatch();
+
+    assert_eq!(delete_step_response.status(), Status::NotFound);
+    assert_eq!(
+        delete_step_response.into_json::<Errors>().unwrap(),
+        expected_error
+    );
+
+    let move_step_response = client
+        .put(format!("/api/recipes/{recipe_id}/steps/0"))
+        .json(&json!({"position": 3}))
+        .dispatch();
+
+    assert_eq!(move_step_response.status(), Status::NotFound);
+    assert_eq!(
+        move_step_response.into_json::<Errors>().unwrap(),
+        expected_error
+    );
+
+    let insert_step_response = client
+        .post(format!("/api/recipes/{recipe_id}/steps/4"))
+        .json(&json!({"description": "Etape 5"}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::NotFound);
+    assert_eq!(
+        retrieve_steps(recipe_id, &client),
+        vec!["Etape 1", "Etape 2", "Etape 3"]
+    );
+}
+
+#[rstest]
+fn insert_blank_step_test(create_recipe_with_steps: (i32, Client)) {
+    let (recipe_id, client) = create_recipe_with_steps;
+
+    let insert_step_response = client
+        .post(format!("/api/recipes/{recipe_id}/steps/0"))
+        .json(&json!({"description": "  "}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        insert_step_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![HTTPError {
+                source: Some("/description".to_string()),
+                ..http_error(
+                    Status::UnprocessableEntity,
+                    "blank_step",
+                    "description must not be blank"
+                )
+            }]
+        }
+    );
+    assert_eq!(
+        retrieve_steps(recipe_id, &client),
+        vec!["Etape 1", "Etape 2", "Etape 3"]
+    );
+}
+
+#[rstest]
+fn step_of_non_existing_recipe_test(client: Client) {
+    let insert_step_response = client
+        .post("/api/recipes/1/steps/0")
+        .json(&json!({"description": "Etape 1"}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::NotFound);
+    assert_eq!(
+        insert_step_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::NotFound,
+                "recipe_not_found",
+                "No recipe found with id 1"
+            )]
+        }
+    );
+}
+
+#[rstest]
+fn step_ingredients_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Saucisses aux lentilles",
+                "ingredients": [
+                    "350 g de Lentilles vertes",
+                    "300 g de saucisses de Montbéliard",
+                    "1 oignon",
+                    "2 gousse d'ail"
+                ],
+                "steps": [
+                    "Eplucher et émincer l'oignon. Peler les gousses d'ail.",
+                    "Dans une cocotte, mettre les lentilles, les saucisses et l'oignon.",
+                    "Servir bien chaud."
+                ]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    let ingredient_ids: Vec<i32> = recipe.ingredients.iter().map(|i| i.id).collect();
+
+    let step_ingredient_ids = |recipe: &RecipeWithIngredientsOut| -> Vec<Vec<i32>> {
+        recipe
+            .steps
+            .iter()
+            .map(|step| step.ingredient_ids.clone())
+            .collect()
+    };
+
+    let expected_step_ingredient_ids = vec![
+        vec![ingredient_ids[2], ingredient_ids[3]],
+        vec![ingredient_ids[0], ingredient_ids[1], ingredient_ids[2]],
+        vec![],
+    ];
+
+    assert_eq!(step_ingredient_ids(&recipe), expected_step_ingredient_ids);
+
+    let retrieved_recipe = client
+        .get(format!("/api/recipes/{}", recipe.id))
+        .dispatch()
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(
+        step_ingredient_ids(&retrieved_recipe),
+        expected_step_ingredient_ids
+    );
+
+    let insert_step_response = client
+        .post(format!("/api/recipes/{}/steps/2", recipe.id))
+        .json(&json!({"description": "Ajouter l'ail écrasé."}))
+        .dispatch();
+
+    assert_eq!(insert_step_response.status(), Status::Created);
+    assert_eq!(
+        insert_step_response
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data
+            .steps[2]
+            .ingredient_ids,
+        vec![ingredient_ids[3]]
+    );
+}
@@ -0,0 +1,264 @@
+use cooking_book::models::{Data, RecipeWithIngredientsOut, TagOut};
+use cooking_book::response::{Errors, HTTPError};
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rstest::{fixture, rstest};
+use serde_json::json;
+
+mod common;
+use common::{client, http_error};
+
+#[fixture]
+fn create_tagged_recipes(client: Client) -> Client {
+    for (name, tags) in [
+        ("Tapenade", vec!["Végétarien", "Apéritif"]),
+        ("Saucisses aux lentilles", vec!["plat principal"]),
+        ("Gratin de légumes", vec![" végétarien ", "Plat  principal"]),
+    ] {
+        let create_recipe_response = client
+            .post("/api/recipes")
+            .json(&json!({"name": name, "ingredients": ["1 citron"], "steps": [], "tags": tags}))
+            .dispatch();
+
+        assert_eq!(create_recipe_response.status(), Status::Created);
+    }
+
+    client
+}
+
+fn retrieve_tags(client: &Client) -> Vec<TagOut> {
+    client
+        .get("/api/tags")
+        .dispatch()
+        .into_json::<Data<Vec<TagOut>>>()
+        .unwrap()
+        .data
+}
+
+fn retrieve_recipe_names(client: &Client, uri: &str) -> Vec<String> {
+    client
+        .get(uri)
+        .dispatch()
+        .into_json::<Data<Vec<RecipeWithIngredientsOut>>>()
+        .unwrap()
+        .data
+        .into_iter()
+        .map(|recipe| recipe.name)
+        .collect()
+}
+
+#[rstest]
+fn create_recipe_with_tags_test(client: Client) {
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {
+                "name": "Tapenade",
+                "ingredients": ["1 citron"],
+                "steps": [],
+                "tags": ["Végétarien", "apéritif", "végétarien"]
+            }
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::Created);
+
+    let recipe = create_recipe_response
+        .into_json::<Data<RecipeWithIngredientsOut>>()
+        .unwrap()
+        .data;
+
+    assert_eq!(recipe.tags, vec!["apéritif", "végétarien"]);
+    assert_eq!(
+        client
+            .get(format!("/api/recipes/{}", recipe.id))
+            .dispatch()
+            .into_json::<Data<RecipeWithIngredientsOut>>()
+            .unwrap()
+            .data
+            .tags,
+        vec!["apéritif", "végétarien"]
+    );
+}
+
+#[rstest]
+fn retrieve_tags_test(create_tagged_recipes: Client) {
+    let client = create_tagged_recipes;
+
+    assert_eq!(
+        retrieve_tags(&client),
+        vec![
+            TagOut {
+                id: 2,
+                name: "apéritif".to_string(),
+                recipe_count: 1
+            },
+            TagOut {
+                id: 3,
+                name: "plat principal".to_string(),
+                recipe_count: 2
+            },
+            TagOut {
+                id: 1,
+                name: "végétarien".to_string(),
+                recipe_count: 2
+            },
+        ]
+    );
+}
+
+#[rstest]
+fn filter_recipes_by_tag_test(create_tagged_recipes: Client) {
+    let client = create_tagged_recipes;
+
+    assert_eq!(
+        retrieve_recipe_names(&client, "/api/recipes?tag=vegetarien"),
+        vec!["Tapenade", "Gratin de légumes"]
+    );
+    assert_eq!(
+        retrieve_recipe_names(&client, "/api/recipes?tag=plat%20principal&sort=name"),
+        vec!["Gratin de légumes", "Saucisses aux lentilles"]
+    );
+    assert!(retrieve_recipe_names(&client, "/api/recipes?tag=dessert").is_empty());
+}
+
+#[rstest]
+fn rename_tag_test(create_tagged_recipes: Client) {
+    let client = create_tagged_recipes;
+
+    let rename_tag_response = client
+        .put("/api/tags/2")
+        .json(&json!({"name": "Apéro"}))
+        .dispatch();
+
+    assert_eq!(rename_tag_response.status(), Status::Ok);
+    assert_eq!(
+        rename_tag_response
+            .into_json::<Data<TagOut>>()
+            .unwrap()
+            .data,
+        TagOut {
+            id: 2,
+            name: "apéro".to_string(),
+            recipe_count: 1
+        }
+    );
+    assert_eq!(
+        retrieve_recipe_names(&client, "/api/recipes?tag=apero"),
+        vec!["Tapenade"]
+    );
+
+    let rename_to_existing_tag_response = client
+        .put("/api/tags/2")
+        .json(&json!({"name": "Vegetarien"}))
+        .dispatch();
+
+    assert_eq!(rename_to_existing_tag_response.status(), Status::Conflict);
+
+    let rename_non_existing_tag_response = client
+        .put("/api/tags/10")
+        .json(&json!({"name": "Dessert"}))
+        .dispatch();
+
+    assert_eq!(rename_non_existing_tag_response.status(), Status::NotFound);
+    assert_eq!(
+        rename_non_existing_tag_response
+            .into_json::<Errors>()
+            .unwrap(),
+        Errors {
+            errors: vec![http_error(
+                Status::NotFound,
+                "tag_not_found",
+                "No tag found with id 10"
+            )]
+        }
+    );
+}
+
+#[rstest]
+fn tag_without_letters_test(create_tagged_recipes: Client) {
+    let client = create_tagged_recipes;
+    let tags_before = retrieve_tags(&client);
+
+    let create_recipe_response = client
+        .post("/api/recipes")
+        .json(&json!(
+            {"name": "Houmous", "ingredients": ["1 citron"], "steps": [], "tags": ["Dessert", "???"]}
+        ))
+        .dispatch();
+
+    assert_eq!(create_recipe_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        create_recipe_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![HTTPError {
+                source: Some("/tags/1".to_string()),
+                ..http_error(
+                    Status::UnprocessableEntity,
+                    "invalid_tag",
+                    "tags must contain a letter or a digit: ???"
+                )
+            }]
+        }
+    );
+
+    let rename_tag_response = client
+        .put("/api/tags/2")
+        .json(&json!({"name": "!!!"}))
+        .dispatch();
+
+    assert_eq!(rename_tag_response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        rename_tag_response.into_json::<Errors>().unwrap(),
+        Errors {
+            errors: vec![HTTPError {
+                source: Some("/name".to_string()),
+                ..http_error(
+                    Status::UnprocessableEntity,
+                    "invalid_tag",
+                    "name must contain a letter or a digit: !!!"
+                )
+            }]
+        }
+    );
+    assert_eq!(retrieve_tags(&client), tags_before);
+}
+
+#[rstest]
+fn merge_tags_test(create_tagged_recipes: Client) {
+    let client = create_tagged_recipes;
+
+    let merge_tags_response = client.post("/api/tags/1/merge/3").dispatch();
+
+    assert_eq!(merge_tags_response.status(), Status::Ok);
+    assert_eq!(
+        merge_tags_response
+            .into_json::<Data<TagOut>>()
+            .unwrap()
+            .data,
+        TagOut {
+            id: 3,
+            name: "plat principal".to_string(),
+            recipe_count: 3
+        }
+    );
+    assert_eq!(
+        retrieve_tags(&client)
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect::<Vec<String>>(),
+        vec!["apéritif", "plat principal"]
+    );
+
+    let merge_non_existing_tag_response = client.post("/api/tags/1/merge/3").dispatch();
+
+    assert_eq!(merge_non_existing_tag_response.status(), Status::NotFound);
+
+    let merge_into_itself_response = client.post("/api/tags/3/merge/3").dispatch();
+
+    assert_eq!(
+        merge_into_itself_response.status(),
+        Status::UnprocessableEntity
+    );
+}